@@ -0,0 +1,58 @@
+
+//! Compares `UrlSet` against a linear scan of a `Vec<Url>`
+//!
+//! `cargo run --release --example url_set_bench`
+
+extern crate serde_url;
+
+use std::time::Instant;
+
+use serde_url::{Url, UrlSet};
+
+const ENTRIES: usize = 50_000;
+const LOOKUPS: usize = 1_000;
+
+fn is_prefix(prefix: &Url, url: &Url) -> bool {
+    if !prefix.same_origin(url) {
+        return false;
+    }
+    let prefix = prefix.get_path_str().unwrap_or("/");
+    let path = url.get_path_str().unwrap_or("/");
+    if prefix.ends_with('/') {
+        path.starts_with(prefix) && path.len() > prefix.len()
+    } else {
+        path == prefix || path.starts_with(&format!("{}/", prefix))
+    }
+}
+
+fn main() {
+    let urls = (0..ENTRIES)
+        .map(|i| Url::new(&format!("https://host{}.google.com/api/v{}/", i % 100, i)).unwrap())
+        .collect::<Vec<Url>>();
+    let lookups = (0..LOOKUPS)
+        .map(|i| Url::new(&format!("https://host{}.google.com/api/v{}/users/{}", i % 100, i * 37, i)).unwrap())
+        .collect::<Vec<Url>>();
+
+    let start = Instant::now();
+    let set = urls.iter().cloned().collect::<UrlSet>();
+    println!("built UrlSet of {} entries in {:?}", set.len(), start.elapsed());
+
+    let start = Instant::now();
+    let mut found = 0;
+    for url in &lookups {
+        found += set.contains_prefix_of(url).is_some() as usize;
+    }
+    let set_time = start.elapsed();
+
+    let start = Instant::now();
+    let mut scanned = 0;
+    for url in &lookups {
+        scanned += urls.iter().any(|prefix| is_prefix(prefix, url)) as usize;
+    }
+    let scan_time = start.elapsed();
+
+    assert_eq!(found, scanned);
+    println!("{} lookups ({} matched)", LOOKUPS, found);
+    println!("UrlSet::contains_prefix_of {:?}", set_time);
+    println!("Vec<Url> scan              {:?}", scan_time);
+}
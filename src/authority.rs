@@ -0,0 +1,141 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::serde;
+use super::url;
+use super::errors::{PortErrorReason, PortText, UrlFault};
+use super::internal::Host;
+
+/// `parse_authority` validates a bare `host[:port]`, without a
+/// scheme. The host may be a domain, an IPv4 address, or a
+/// bracketed IPv6 address, it is validated (and IDNA encoded) the
+/// same way the url parser validates the host of a URL.
+///
+/// Userinfo (`user@host`), paths, queries, and fragments are
+/// rejected as `InvalidDomainCharacter`.
+///
+/// ```
+/// use serde_url::{parse_authority, Host, PortErrorReason, PortText, UrlFault};
+///
+/// let (host, port) = parse_authority("Example.com:8443").unwrap();
+/// assert_eq!(host, Host::Domain("example.com".to_string()));
+/// assert_eq!(port, Some(8443));
+///
+/// let (host, port) = parse_authority("[::1]:53").unwrap();
+/// assert_eq!(host, Host::Ipv6("::1".parse().unwrap()));
+/// assert_eq!(port, Some(53));
+///
+/// assert_eq!(parse_authority("user@example.com"), Err(UrlFault::InvalidDomainCharacter));
+/// assert_eq!(
+///     parse_authority("example.com:70000"),
+///     Err(UrlFault::InvalidPort { reason: PortErrorReason::OutOfRange(70000), text: PortText::new("70000") }));
+/// ```
+pub fn parse_authority(input: &str) -> Result<(Host<String>, Option<u16>), UrlFault> {
+    if input.contains(['@', '/', '\\', '?', '#']) {
+        return Err(UrlFault::InvalidDomainCharacter);
+    }
+    let (host, port) = if input.starts_with('[') {
+        match input.find(']') {
+            Option::Some(end) => {
+                let rest = &input[end + 1..];
+                let port = match rest.strip_prefix(':') {
+                    Option::Some(port) => Some(port),
+                    Option::None if rest.is_empty() => None,
+                    Option::None => return Err(UrlFault::InvalidIpv6Address),
+                };
+                (&input[..end + 1], port)
+            }
+            Option::None => return Err(UrlFault::InvalidIpv6Address),
+        }
+    } else {
+        let mut parts = input.splitn(2, ':');
+        (parts.next().unwrap_or(""), parts.next())
+    };
+    if host.is_empty() {
+        return Err(UrlFault::EmptyHost);
+    }
+    let port = match port {
+        Option::None => None,
+        Option::Some(port) => {
+            if let Option::Some(reason) = PortErrorReason::from_port_text(port) {
+                return Err(UrlFault::InvalidPort { reason, text: PortText::new(port) });
+            }
+            port.parse::<u16>().ok()
+        }
+    };
+    let host = match url::Host::parse(host)? {
+        url::Host::Domain(domain) => Host::Domain(domain),
+        url::Host::Ipv4(addr) => Host::Ipv4(addr),
+        url::Host::Ipv6(addr) => Host::Ipv6(addr),
+    };
+    Ok((host, port))
+}
+
+/// A validated `host[:port]`, see `parse_authority`
+///
+/// This serializes as the `host[:port]` string.
+///
+/// ```
+/// use serde_url::HostAndPort;
+///
+/// let authority: HostAndPort = "10.0.0.1:8080".parse().unwrap();
+/// assert_eq!(authority.port, Some(8080));
+/// assert_eq!(authority.to_string(), "10.0.0.1:8080");
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct HostAndPort {
+    pub host: Host<String>,
+    pub port: Option<u16>,
+}
+impl FromStr for HostAndPort {
+    type Err = UrlFault;
+    fn from_str(input: &str) -> Result<HostAndPort, UrlFault> {
+        let (host, port) = parse_authority(input)?;
+        Ok(HostAndPort { host, port })
+    }
+}
+impl fmt::Display for HostAndPort {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.host {
+            Host::Domain(ref domain) => write!(f, "{}", domain)?,
+            Host::Ipv4(ref addr) => write!(f, "{}", addr)?,
+            Host::Ipv6(ref addr) => write!(f, "[{}]", addr)?,
+        };
+        match self.port {
+            Option::Some(port) => write!(f, ":{}", port),
+            Option::None => Ok(()),
+        }
+    }
+}
+impl serde::Serialize for HostAndPort {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+struct HostAndPortVisitor;
+impl<'de> serde::de::Visitor<'de> for HostAndPortVisitor {
+    type Value = HostAndPort;
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "host[:port]")
+    }
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        HostAndPort::from_str(value)
+            .map_err(|e| format!("{:?}", e))
+            .map_err(serde::de::Error::custom)
+    }
+}
+impl<'de> serde::Deserialize<'de> for HostAndPort {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_str(HostAndPortVisitor)
+    }
+}
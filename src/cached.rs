@@ -0,0 +1,69 @@
+//! Serde with-module which parses through the global `ParserCache`
+//!
+//! Deserialization is `Url::new_cached`, so repeated inputs share
+//! one parsed `Url` once a cache is installed with
+//! `set_global_parser_cache`. Without one this is the same as
+//! deserializing a `Url`. The deserialize validator still applies.
+//!
+//! ```
+//! #[macro_use]
+//! extern crate serde_derive;
+//! extern crate serde_json;
+//! extern crate serde_url;
+//!
+//! use serde_url::Url;
+//!
+//! #[derive(Deserialize)]
+//! struct Event {
+//!     #[serde(with = "serde_url::cached")]
+//!     source: Url,
+//! }
+//!
+//! # fn main() {
+//! let event: Event = serde_json::from_str(r#"{"source":"https://google.com/"}"#).unwrap();
+//! assert_eq!(event.source, "https://google.com/");
+//! # }
+//! ```
+
+use std::fmt;
+
+use super::serde;
+use super::parser_cache;
+use super::validator;
+use super::Url;
+
+/// `serialize` writes the normalized string
+pub fn serialize<S>(url: &Url, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serde::Serialize::serialize(url, serializer)
+}
+
+/// `deserialize` parses the URL through the global cache
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Url, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    deserializer.deserialize_str(CachedVisitor)
+}
+
+struct CachedVisitor;
+impl<'de> serde::de::Visitor<'de> for CachedVisitor {
+    type Value = Url;
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Url")
+    }
+    fn visit_str<E>(self, value: &str) -> Result<Url, E>
+    where
+        E: serde::de::Error,
+    {
+        let url = parser_cache::parse_global(value)
+            .map_err(|e| format!("{:?}", e))
+            .map_err(serde::de::Error::custom)?;
+        validator::validate(&url)
+            .map_err(|msg| format!("{} ({})", msg, url))
+            .map_err(serde::de::Error::custom)?;
+        Ok(url)
+    }
+}
@@ -0,0 +1,140 @@
+//! Readers for formats older versions of a `Url` were stored in
+
+/// Serde with-module for URLs stored as a 3-tuple
+///
+/// Some archives hold URLs as `(scheme, authority, path_query)`.
+/// `deserialize` accepts that tuple, or the modern string form, and
+/// validates the reassembled URL, including the installed
+/// `DeserializeValidator`. `serialize` always writes the
+/// string form, so archives converge on it as they are re-written.
+///
+/// The tuple is reassembled as `scheme://authority path_query`, or
+/// `scheme:path_query` when the authority is empty and the path
+/// does not start with `/` (`mailto:`, `data:`, etc.).
+///
+/// # Non-self-describing formats
+///
+/// Telling a string from a tuple requires `deserialize_any`, which
+/// formats such as bincode do not support. Archives in those formats
+/// hold one form or the other, read them with `tuple_only`, and
+/// re-write them with this module.
+///
+/// ```
+/// #[macro_use]
+/// extern crate serde_derive;
+/// extern crate serde_json;
+/// extern crate serde_url;
+///
+/// use serde_url::Url;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Record {
+///     #[serde(with = "serde_url::compat::legacy_tuple")]
+///     url: Url,
+/// }
+///
+/// # fn main() {
+/// let old = r#"{"url":["https","google.com:8080","/search?q=1"]}"#;
+/// let record: Record = serde_json::from_str(old).unwrap();
+/// assert_eq!(record.url, "https://google.com:8080/search?q=1");
+/// assert_eq!(serde_json::to_string(&record).unwrap(), r#"{"url":"https://google.com:8080/search?q=1"}"#);
+/// # }
+/// ```
+pub mod legacy_tuple {
+    use std::fmt;
+
+    use super::super::serde;
+    use super::super::serde::de::{Error, SeqAccess, Visitor};
+    use super::super::validator;
+    use super::super::Url;
+
+    const EXPECTING: &str = "a URL string, or a (scheme, authority, path_query) tuple";
+
+    /// `serialize` writes the normalized string
+    pub fn serialize<S>(url: &Url, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serde::Serialize::serialize(url, serializer)
+    }
+
+    /// `deserialize` reads either the string or the tuple form
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Url, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(LegacyVisitor)
+    }
+
+    /// Serde with-module which only reads the tuple form, for
+    /// formats without `deserialize_any` (see `legacy_tuple`).
+    /// `serialize` still writes the string form.
+    pub mod tuple_only {
+        use super::super::super::serde;
+        use super::super::super::Url;
+        use super::LegacyVisitor;
+
+        pub use super::serialize;
+
+        /// `deserialize` reads the `(scheme, authority, path_query)` tuple
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<Url, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            deserializer.deserialize_tuple(3, LegacyVisitor)
+        }
+    }
+
+    /// `assemble` rebuilds the URL string from its tuple parts
+    fn assemble(scheme: &str, authority: &str, path_query: &str) -> String {
+        if authority.is_empty() && !path_query.starts_with('/') {
+            format!("{}:{}", scheme, path_query)
+        } else {
+            format!("{}://{}{}", scheme, authority, path_query)
+        }
+    }
+
+    struct LegacyVisitor;
+    impl<'de> Visitor<'de> for LegacyVisitor {
+        type Value = Url;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str(EXPECTING)
+        }
+
+        fn visit_str<E: Error>(self, value: &str) -> Result<Url, E> {
+            let url = Url::new(&value).map_err(E::custom)?;
+            validated(url)
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Url, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let mut parts: Vec<String> = Vec::with_capacity(3);
+            while let Option::Some(part) = seq.next_element::<String>()? {
+                parts.push(part);
+                if parts.len() > 3 {
+                    break;
+                }
+            }
+            if parts.len() != 3 {
+                let found = if parts.len() > 3 { "more than 3".to_string() } else { parts.len().to_string() };
+                return Err(A::Error::custom(format!(
+                    "legacy URL tuple must have 3 elements (scheme, authority, path_query), found {}",
+                    found)));
+            }
+            let input = assemble(&parts[0], &parts[1], &parts[2]);
+            let url = Url::new(&input).map_err(|fault| A::Error::custom(format!("{:?}: {}", input, fault)))?;
+            validated(url)
+        }
+    }
+
+    /// `validated` runs the `DeserializeValidator`, as every other
+    /// deserializer does
+    fn validated<E: Error>(url: Url) -> Result<Url, E> {
+        validator::validate(&url)
+            .map_err(|msg| E::custom(format!("{} ({})", msg, url)))?;
+        Ok(url)
+    }
+}
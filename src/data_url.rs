@@ -0,0 +1,148 @@
+
+use std::fmt;
+
+use super::base64::Engine;
+use super::base64::engine::general_purpose::STANDARD;
+use super::url::percent_encoding::{percent_decode, percent_encode, EncodeSet, DEFAULT_ENCODE_SET};
+
+use super::errors::UrlFault;
+use super::Url;
+
+/// The MIME type assumed when a data URL does not declare one
+pub const DEFAULT_DATA_MIME_TYPE: &str = "text/plain;charset=US-ASCII";
+
+/// The decoded contents of a `data:` URL
+///
+/// ```
+/// use serde_url::Url;
+///
+/// let url = Url::new(&"data:text/html;base64,PGgxPmhpPC9oMT4=").unwrap();
+/// let data = url.try_parse_data_url().unwrap();
+/// assert_eq!(data.mime_type, "text/html");
+/// assert!(data.is_base64);
+/// assert_eq!(data.data, b"<h1>hi</h1>");
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct DataUrl {
+    /// the MIME type including any parameters, but without the
+    /// `;base64` flag.
+    pub mime_type: String,
+    /// if the payload was (or should be) base64 encoded
+    pub is_base64: bool,
+    /// the decoded payload
+    pub data: Vec<u8>,
+}
+impl DataUrl {
+    /// `parse` decodes the contents of a `data:` URL
+    pub fn parse(url: &Url) -> Result<DataUrl, UrlFault> {
+        if !url.scheme_is("data") {
+            return Err(UrlFault::NotADataUrl);
+        }
+
+        // everything after `data:` up until the fragment
+        let body = &url.get_string()["data:".len()..];
+        let body = match url.get_fragment() {
+            Option::Some(fragment) => &body[..body.len() - fragment.len() - 1],
+            Option::None => body,
+        };
+        let comma = match body.find(',') {
+            Option::Some(comma) => comma,
+            Option::None => return Err(UrlFault::DataUrlMalformed),
+        };
+        let (header, payload) = (&body[..comma], &body[comma + 1..]);
+
+        let header = percent_decode(header.as_bytes()).decode_utf8_lossy();
+        let header = header.trim();
+        let (mime_type, is_base64) = match header.rfind(';') {
+            Option::Some(index) if header[index + 1..].trim().eq_ignore_ascii_case("base64") => {
+                (header[..index].trim(), true)
+            }
+            _ => (header, false),
+        };
+        let mime_type = if mime_type.is_empty() {
+            DEFAULT_DATA_MIME_TYPE.to_string()
+        } else {
+            mime_type.to_string()
+        };
+
+        let decoded = percent_decode(payload.as_bytes()).collect::<Vec<u8>>();
+        let data = if is_base64 {
+            let stripped = decoded
+                .into_iter()
+                .filter(|byte| !byte.is_ascii_whitespace())
+                .collect::<Vec<u8>>();
+            STANDARD
+                .decode(&stripped)
+                .map_err(|_| UrlFault::DataUrlMalformed)?
+        } else {
+            decoded
+        };
+
+        Ok(DataUrl {
+            mime_type,
+            is_base64,
+            data,
+        })
+    }
+
+    /// `into_url` encodes this back into a `data:` URL. The MIME type
+    /// keeps its parameters, anything which would end the header
+    /// early (`,`, `#`, `%`) is percent encoded.
+    ///
+    /// ```
+    /// use serde_url::DataUrl;
+    ///
+    /// let data = DataUrl {
+    ///     mime_type: "text/plain;charset=utf-8".to_string(),
+    ///     is_base64: false,
+    ///     data: b"100% #1".to_vec(),
+    /// };
+    /// let url = data.clone().into_url().unwrap();
+    /// assert_eq!(url, "data:text/plain;charset=utf-8,100%25%20%231");
+    /// assert_eq!(url.try_parse_data_url().unwrap(), data);
+    /// ```
+    pub fn into_url(self) -> Result<Url, UrlFault> {
+        let mime_type = percent_encode(self.mime_type.as_bytes(), MIME_ENCODE_SET);
+        let payload = if self.is_base64 {
+            STANDARD.encode(&self.data)
+        } else {
+            percent_encode(&self.data, DATA_ENCODE_SET).to_string()
+        };
+        let flag = if self.is_base64 { ";base64" } else { "" };
+        let url = format!("data:{}{},{}", mime_type, flag, payload);
+        Url::new(&url)
+    }
+}
+impl fmt::Debug for DataUrl {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "DataUrl {{ mime_type: {:?}, is_base64: {}, data: [{} bytes] }}",
+            self.mime_type,
+            self.is_base64,
+            self.data.len()
+        )
+    }
+}
+
+/// `DATA_ENCODE_SET` is the default encode set plus `%`, so
+/// payloads survive the round trip.
+#[derive(Copy, Clone)]
+struct DataEncodeSet;
+const DATA_ENCODE_SET: DataEncodeSet = DataEncodeSet;
+impl EncodeSet for DataEncodeSet {
+    fn contains(&self, byte: u8) -> bool {
+        byte == b'%' || DEFAULT_ENCODE_SET.contains(byte)
+    }
+}
+
+/// `MIME_ENCODE_SET` is `DATA_ENCODE_SET` plus `,`, which would
+/// otherwise end the header inside a parameter value.
+#[derive(Copy, Clone)]
+struct MimeEncodeSet;
+const MIME_ENCODE_SET: MimeEncodeSet = MimeEncodeSet;
+impl EncodeSet for MimeEncodeSet {
+    fn contains(&self, byte: u8) -> bool {
+        byte == b',' || DATA_ENCODE_SET.contains(byte)
+    }
+}
@@ -0,0 +1,68 @@
+use super::Url;
+
+/// Hash functions available to `Url::get_digest`
+///
+/// Each is enabled by its own feature, `digest-sha256`,
+/// `digest-sha1`, and `digest-md5`.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum DigestAlgorithm {
+    #[cfg(feature = "digest-sha256")]
+    Sha256,
+    #[cfg(feature = "digest-sha1")]
+    Sha1,
+    #[cfg(feature = "digest-md5")]
+    Md5,
+}
+
+impl Url {
+    /// `get_digest` hashes the normalized string, `get_string()`.
+    ///
+    /// SHA-1 and MD5 are only suitable for identifiers (ETags, cache
+    /// keys, deduplication), not for anything security related.
+    ///
+    /// ```
+    /// use serde_url::{Url, DigestAlgorithm};
+    ///
+    /// # #[cfg(feature = "digest-sha256")] {
+    /// let url = Url::new(&"https://google.com").unwrap();
+    /// assert_eq!(url.get_digest(DigestAlgorithm::Sha256).len(), 32);
+    /// # }
+    /// ```
+    pub fn get_digest(&self, algorithm: DigestAlgorithm) -> Vec<u8> {
+        let input = self.get_string().as_bytes();
+        match algorithm {
+            #[cfg(feature = "digest-sha256")]
+            DigestAlgorithm::Sha256 => {
+                use sha2::Digest;
+                sha2::Sha256::digest(input).to_vec()
+            }
+            #[cfg(feature = "digest-sha1")]
+            DigestAlgorithm::Sha1 => {
+                use sha1::Digest;
+                sha1::Sha1::digest(input).to_vec()
+            }
+            #[cfg(feature = "digest-md5")]
+            DigestAlgorithm::Md5 => {
+                use md5::Digest;
+                md5::Md5::digest(input).to_vec()
+            }
+        }
+    }
+
+    /// `get_digest_hex` returns `get_digest` as lowercase hex
+    ///
+    /// ```
+    /// use serde_url::{Url, DigestAlgorithm};
+    ///
+    /// # #[cfg(feature = "digest-md5")] {
+    /// let url = Url::new(&"https://google.com").unwrap();
+    /// assert_eq!(url.get_digest_hex(DigestAlgorithm::Md5), "f82438a9862a39d642f39887b3e8e5b4");
+    /// # }
+    /// ```
+    pub fn get_digest_hex(&self, algorithm: DigestAlgorithm) -> String {
+        self.get_digest(algorithm)
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+}
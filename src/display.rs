@@ -0,0 +1,57 @@
+use std::fmt;
+
+use super::Url;
+
+/// `Display` of an optional `Url`, see `display_or`
+#[derive(Clone, Copy, Debug)]
+pub struct DisplayOr<'a> {
+    url: Option<&'a Url>,
+    placeholder: &'a str,
+}
+impl<'a> fmt::Display for DisplayOr<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.url {
+            Option::Some(url) => f.write_str(url.get_string()),
+            Option::None => f.write_str(self.placeholder),
+        }
+    }
+}
+
+/// `display_or` displays the URL, or `placeholder` when there is
+/// none, without allocating.
+///
+/// ```
+/// use serde_url::{Url, display_or};
+///
+/// let url = Url::new(&"https://google.com").unwrap();
+/// assert_eq!(format!("{}", display_or(Some(&url), "-")), "https://google.com/");
+/// assert_eq!(format!("{}", display_or(None, "-")), "-");
+/// ```
+pub fn display_or<'a>(url: Option<&'a Url>, placeholder: &'a str) -> DisplayOr<'a> {
+    DisplayOr { url, placeholder }
+}
+
+/// `display_or` as a method of `Option<&Url>` and `Option<Url>`
+///
+/// ```
+/// use serde_url::{Url, OptionUrlExt};
+///
+/// let referer: Option<Url> = None;
+/// let origin = Url::new(&"https://google.com").ok();
+/// assert_eq!(format!("{} {}", referer.display_or("-"), origin.display_or("-")), "- https://google.com/");
+/// ```
+pub trait OptionUrlExt {
+    /// `display_or` displays the URL, or `placeholder` when there
+    /// is none.
+    fn display_or<'a>(&'a self, placeholder: &'a str) -> DisplayOr<'a>;
+}
+impl<'u> OptionUrlExt for Option<&'u Url> {
+    fn display_or<'a>(&'a self, placeholder: &'a str) -> DisplayOr<'a> {
+        display_or(self.map(|url| url), placeholder)
+    }
+}
+impl OptionUrlExt for Option<Url> {
+    fn display_or<'a>(&'a self, placeholder: &'a str) -> DisplayOr<'a> {
+        display_or(self.as_ref(), placeholder)
+    }
+}
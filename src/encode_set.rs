@@ -0,0 +1,95 @@
+use std::fmt::Write;
+
+/// The bytes to percent encode when adding a path segment or a query
+/// parameter to a URL
+///
+/// The defaults encode everything except the RFC 3986 unreserved
+/// characters (`A-Z a-z 0-9 - . _ ~`), plus `:` and `@` in paths and
+/// `:`, `@`, `/`, and `?` in queries. `allow` leaves more characters
+/// as they are, for systems which give them meaning.
+///
+/// Characters which would change the structure of the URL, or which
+/// the parser would encode when the URL is parsed again, are always
+/// encoded whatever the set allows:
+///
+/// * everywhere: controls, space, non-ASCII, `"`, `#`, `%`, `<`,
+///   `>`, `` ` ``, `{`, and `}`
+/// * in a path segment: `/`, `\`, and `?`
+/// * in a query parameter: `&`, `=`, and `+`
+///
+/// So a `Url` built with any `EncodeSet` parses again to an equal
+/// `Url`.
+///
+/// ```
+/// use serde_url::{Url, EncodeSet};
+///
+/// let cdn = EncodeSet::path_default().allow(',').allow('$');
+/// let url = Url::new(&"https://cdn.google.com/images/").unwrap();
+/// let resized = url.push_path_segment_with("w_100,h_50,$fit", &cdn).unwrap();
+/// assert_eq!(resized, "https://cdn.google.com/images/w_100,h_50,$fit");
+/// assert_eq!(url.push_path_segment("w_100,h_50").unwrap(), "https://cdn.google.com/images/w_100%2Ch_50");
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct EncodeSet {
+    ascii: u128,
+}
+impl EncodeSet {
+    /// `path_default` is the set used by `Url::push_path_segment`
+    pub fn path_default() -> EncodeSet {
+        EncodeSet::unreserved().allow(':').allow('@')
+    }
+
+    /// `query_default` is the set used by `Url::with_query_param`
+    pub fn query_default() -> EncodeSet {
+        EncodeSet::unreserved().allow(':').allow('@').allow('/').allow('?')
+    }
+
+    /// `unreserved` encodes every ASCII byte but `A-Z a-z 0-9 - . _ ~`
+    fn unreserved() -> EncodeSet {
+        let mut set = EncodeSet { ascii: u128::MAX };
+        for byte in 0u8..128 {
+            if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+                set.ascii &= !(1 << byte);
+            }
+        }
+        set
+    }
+
+    /// `allow` leaves `c` unencoded. Non-ASCII characters, and those
+    /// which are always encoded, are ignored.
+    pub fn allow(mut self, c: char) -> EncodeSet {
+        if c.is_ascii() {
+            self.ascii &= !(1 << (c as u32));
+        }
+        self
+    }
+
+    /// `contains` checks if `byte` is encoded by this set, before the
+    /// characters which are always encoded are taken into account.
+    pub fn contains(&self, byte: u8) -> bool {
+        byte >= 128 || self.ascii & (1 << byte) != 0
+    }
+
+    /// `encode_path_segment` encodes a single path segment
+    pub fn encode_path_segment(&self, segment: &str) -> String {
+        self.encode(segment, b"/\\?")
+    }
+
+    /// `encode_query_component` encodes a query key or value
+    pub fn encode_query_component(&self, component: &str) -> String {
+        self.encode(component, b"&=+")
+    }
+
+    fn encode(&self, input: &str, structural: &[u8]) -> String {
+        let mut output = String::with_capacity(input.len());
+        for &byte in input.as_bytes() {
+            let always = byte <= b' ' || byte == 0x7F || b"\"#%<>`{}".contains(&byte) || structural.contains(&byte);
+            if always || self.contains(byte) {
+                let _ = write!(output, "%{:02X}", byte);
+            } else {
+                output.push(byte as char);
+            }
+        }
+        output
+    }
+}
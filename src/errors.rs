@@ -1,6 +1,9 @@
 
+use std::ascii;
 use std::error::Error;
 use std::fmt;
+use std::io;
+use std::str;
 
 /// Returns error related to URL faults
 ///
@@ -14,14 +17,18 @@ pub enum UrlFault {
      */
     EmptyHost,
     IdnaError,
-    InvalidPort,
+    /// `reason` and `text` are recovered from the input when it is
+    /// known, see `PortErrorReason`.
+    InvalidPort { reason: PortErrorReason, text: PortText },
     InvalidIpv4Address,
     InvalidIpv6Address,
     InvalidDomainCharacter,
     RelativeUrlWithoutBase,
     RelativeUrlWithCannotBeABaseUrlIsABaseUrl,
     SetHostOnCannotBeABaseUrl,
-    Overflow,
+    /// `input_len` is the length in bytes of the offending input,
+    /// it is `0` when the input is unknown (see `from_parse_error`).
+    Overflow { input_len: usize },
 
     /*
      * Internal Errors from internal
@@ -32,36 +39,347 @@ pub enum UrlFault {
     PasswordUtf8,
     PathUtf8,
     FullQueryUtf8,
+
+    /*
+     * Errors from inspecting the
+     * contents of a URL
+     *
+     */
+    NotADataUrl,
+    DataUrlMalformed,
+
+    /*
+     * Errors from building a new
+     * URL out of an existing one
+     *
+     */
+    InvalidScheme,
+    InvalidPathSegment,
+    RelativeFilePath,
+    InvalidFilePath,
+    InvalidQueryParameter,
+    InvalidBase64,
+
+    /*
+     * Errors from `ParseOptions` rejecting
+     * an otherwise valid URL
+     *
+     */
+    FragmentForbidden,
+    CredentialsForbidden,
+    QueryForbidden,
+
+    /*
+     * Errors from the `DeserializeValidator`
+     * rejecting an otherwise valid URL
+     *
+     */
+    ValidatorRejected,
+
+    /*
+     * Errors from writing a URL into
+     * an HTTP request
+     *
+     */
+    InvalidHttpMethod,
+    InvalidHttpVersion,
 }
-impl fmt::Display for UrlFault {
-    fn fmt(&self,f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{:?} {}", self, self.description())
+impl UrlFault {
+    /// `from_parse_error` converts a `url::ParseError` while
+    /// retaining details about the input which caused it.
+    ///
+    /// The plain `From` conversion does not have access to the
+    /// input, so length related faults report an `input_len` of `0`.
+    pub fn from_parse_error(err: url::ParseError, input: &str) -> UrlFault {
+        match UrlFault::from(err) {
+            UrlFault::Overflow { .. } => UrlFault::Overflow { input_len: input.len() },
+            UrlFault::InvalidPort { reason, text } => {
+                let found = port_text(input)
+                    .and_then(|port| PortErrorReason::from_port_text(port).map(|reason| (reason, port)));
+                match found {
+                    Option::Some((reason, port)) => UrlFault::InvalidPort { reason, text: PortText::new(port) },
+                    Option::None => UrlFault::InvalidPort { reason, text },
+                }
+            }
+            fault => fault,
+        }
+    }
+
+    /// `is_input_problem` reports if the input simply is not a valid
+    /// URL. These are best rejected, and reported to the user.
+    pub fn is_input_problem(&self) -> bool {
+        match *self {
+            UrlFault::EmptyHost |
+            UrlFault::IdnaError |
+            UrlFault::InvalidPort { .. } |
+            UrlFault::InvalidIpv4Address |
+            UrlFault::InvalidIpv6Address |
+            UrlFault::InvalidDomainCharacter |
+            UrlFault::RelativeUrlWithoutBase |
+            UrlFault::RelativeUrlWithCannotBeABaseUrlIsABaseUrl |
+            UrlFault::Overflow { .. } |
+            UrlFault::InvalidScheme |
+            UrlFault::InvalidPathSegment |
+            UrlFault::RelativeFilePath |
+            UrlFault::InvalidFilePath |
+            UrlFault::InvalidQueryParameter |
+            UrlFault::InvalidBase64 |
+            UrlFault::FragmentForbidden |
+            UrlFault::CredentialsForbidden |
+            UrlFault::QueryForbidden |
+            UrlFault::ValidatorRejected |
+            UrlFault::InvalidHttpMethod |
+            UrlFault::InvalidHttpVersion => true,
+            UrlFault::SetHostOnCannotBeABaseUrl |
+            UrlFault::UserNameUtf8 |
+            UrlFault::PasswordUtf8 |
+            UrlFault::PathUtf8 |
+            UrlFault::FullQueryUtf8 |
+            UrlFault::NotADataUrl |
+            UrlFault::DataUrlMalformed => false,
+        }
+    }
+
+    /// `is_component_problem` reports if the URL parsed, but one of
+    /// its components could not be extracted or interpreted. These
+    /// are worth logging, and investigating.
+    ///
+    /// `SetHostOnCannotBeABaseUrl` is neither an input nor a component
+    /// problem, it is a misuse of a URL.
+    pub fn is_component_problem(&self) -> bool {
+        matches!(
+            *self,
+            UrlFault::UserNameUtf8 |
+            UrlFault::PasswordUtf8 |
+            UrlFault::PathUtf8 |
+            UrlFault::FullQueryUtf8 |
+            UrlFault::NotADataUrl |
+            UrlFault::DataUrlMalformed
+        )
+    }
+
+    /// `message` is the human readable text of the fault
+    fn message(&self) -> &'static str {
+        match *self {
+            UrlFault::EmptyHost => "host does not exist",
+            UrlFault::IdnaError => "domain name or label failed process set, it does not meet validity criteria",
+            UrlFault::InvalidPort { .. } => "port value is invalid",
+            UrlFault::InvalidIpv4Address => "ipv4 address is not valid",
+            UrlFault::InvalidIpv6Address => "ipv6 address is not valid",
+            UrlFault::InvalidDomainCharacter => "domain name contains invalid character",
+            UrlFault::RelativeUrlWithoutBase => "not resolve URL relative path",
+            UrlFault::RelativeUrlWithCannotBeABaseUrlIsABaseUrl => "URL states it is not a base URL, but it is a base URL",
+            UrlFault::SetHostOnCannotBeABaseUrl => "URL is a base URL, but cannot be",
+            UrlFault::Overflow { .. } => "URL length overflowed while parsing",
+            UrlFault::UserNameUtf8 => "URL contains a username which cannot be represented with UTF8",
+            UrlFault::PasswordUtf8 => "URL contains a password which cannot be represented with UTF8",
+            UrlFault::PathUtf8 => "URL contains a path which cannot be represented with UTF8",
+            UrlFault::FullQueryUtf8 => "URL contains a query string which cannot be represented with UTF8",
+            UrlFault::NotADataUrl => "URL does not have the `data` scheme",
+            UrlFault::InvalidPathSegment => "path segment cannot be `.` or `..`",
+            UrlFault::InvalidScheme => "scheme is invalid, or cannot be used with this URL",
+            UrlFault::RelativeFilePath => "file path is relative, and could not be made absolute",
+            UrlFault::InvalidFilePath => "file path cannot be represented as a file URL",
+            UrlFault::InvalidQueryParameter => "query parameter must be `key=value` or `key`, with a non-empty key",
+            UrlFault::InvalidBase64 => "input is not URL safe base64 of a UTF8 string",
+            UrlFault::FragmentForbidden => "URL contains a fragment, which is not allowed",
+            UrlFault::CredentialsForbidden => "URL contains a username or password, which is not allowed",
+            UrlFault::QueryForbidden => "URL contains a query, which is not allowed",
+            UrlFault::ValidatorRejected => "URL was rejected by the deserialize validator",
+            UrlFault::InvalidHttpMethod => "HTTP method must be a token, such as `GET`",
+            UrlFault::InvalidHttpVersion => "HTTP version must be `HTTP/` followed by a number, such as `HTTP/1.1`",
+            UrlFault::DataUrlMalformed => "data URL is missing a `,` or contains an invalid payload",
+        }
+    }
+
+    /// `parse_error` returns the `url::ParseError` this fault was
+    /// uplifted from, if it came from the url parser.
+    pub fn parse_error(&self) -> Option<url::ParseError> {
+        match *self {
+            UrlFault::EmptyHost => Some(url::ParseError::EmptyHost),
+            UrlFault::IdnaError => Some(url::ParseError::IdnaError),
+            UrlFault::InvalidPort { .. } => Some(url::ParseError::InvalidPort),
+            UrlFault::InvalidIpv4Address => Some(url::ParseError::InvalidIpv4Address),
+            UrlFault::InvalidIpv6Address => Some(url::ParseError::InvalidIpv6Address),
+            UrlFault::InvalidDomainCharacter => Some(url::ParseError::InvalidDomainCharacter),
+            UrlFault::RelativeUrlWithoutBase => Some(url::ParseError::RelativeUrlWithoutBase),
+            UrlFault::RelativeUrlWithCannotBeABaseUrlIsABaseUrl => {
+                Some(url::ParseError::RelativeUrlWithCannotBeABaseBase)
+            }
+            UrlFault::SetHostOnCannotBeABaseUrl => Some(url::ParseError::SetHostOnCannotBeABaseUrl),
+            UrlFault::Overflow { .. } => Some(url::ParseError::Overflow),
+            _ => None,
+        }
     }
 }
-impl Error for UrlFault {
-    fn description(&self) -> &'static str {
-        match self {
-            &UrlFault::EmptyHost => "host does not exist",
-            &UrlFault::IdnaError => "domain name or label failed process set, it does not meet validity criteria",
-            &UrlFault::InvalidPort => "port value is invalid",
-            &UrlFault::InvalidIpv4Address => "ipv4 address is not valid",
-            &UrlFault::InvalidIpv6Address => "ipv6 address is not valid",
-            &UrlFault::InvalidDomainCharacter => "domain name contains invalid character",
-            &UrlFault::RelativeUrlWithoutBase => "not resolve URL relative path",
-            &UrlFault::RelativeUrlWithCannotBeABaseUrlIsABaseUrl => "URL states it is not a base URL, but it is a base URL",
-            &UrlFault::SetHostOnCannotBeABaseUrl => "URL is a base URL, but cannot be",
-            &UrlFault::Overflow => "URL length overflowed while parsing",
-            &UrlFault::UserNameUtf8 => "URL contains a username which cannot be represented with UTF8",
-            &UrlFault::PasswordUtf8 => "URL contains a password which cannot be represented with UTF8",
-            &UrlFault::PathUtf8 => "URL contains a path which cannot be represented with UTF8",
-            &UrlFault::FullQueryUtf8 => "URL contains a query string which cannot be represented with UTF8",
+/// Why a port was rejected, see `UrlFault::InvalidPort`
+///
+/// The url parser does not say why it rejected a port, so the
+/// reason is recovered from the input. When that is not possible
+/// (the plain `From<url::ParseError>` conversion) it is reported as
+/// `NotNumeric`.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum PortErrorReason {
+    /// the port is numeric but larger than `65535`, values which do
+    /// not fit in a `u64` are reported as `u64::MAX`
+    OutOfRange(u64),
+    /// the port contains something other than ASCII digits
+    NotNumeric,
+    /// a `:` was not followed by a port
+    Empty,
+}
+impl PortErrorReason {
+    /// `from_port_text` classifies the text after the `:` of an
+    /// authority, `None` is returned for a valid port.
+    ///
+    /// ```
+    /// use serde_url::PortErrorReason;
+    ///
+    /// assert_eq!(PortErrorReason::from_port_text("99999"), Some(PortErrorReason::OutOfRange(99999)));
+    /// assert_eq!(PortErrorReason::from_port_text("8a"), Some(PortErrorReason::NotNumeric));
+    /// assert_eq!(PortErrorReason::from_port_text(""), Some(PortErrorReason::Empty));
+    /// assert_eq!(PortErrorReason::from_port_text("8080"), None);
+    /// ```
+    pub fn from_port_text(port: &str) -> Option<PortErrorReason> {
+        if port.is_empty() {
+            return Some(PortErrorReason::Empty);
+        }
+        if !port.bytes().all(|b| b.is_ascii_digit()) {
+            return Some(PortErrorReason::NotNumeric);
+        }
+        match port.parse::<u64>() {
+            Ok(port) if port <= u16::MAX as u64 => None,
+            Ok(port) => Some(PortErrorReason::OutOfRange(port)),
+            Err(_) => Some(PortErrorReason::OutOfRange(u64::MAX)),
         }
     }
-    fn cause(&self) -> Option<&dyn Error> {
-        None
+}
+impl PortErrorReason {
+    /// `describe` explains the reason, using what is known of the
+    /// rejected `text`.
+    ///
+    /// ```
+    /// use serde_url::{PortErrorReason, PortText};
+    ///
+    /// assert_eq!(PortErrorReason::NotNumeric.describe(&PortText::new("8a")), "port is not numeric, `a` is not a digit");
+    /// assert_eq!(PortErrorReason::NotNumeric.describe(&PortText::default()), "port is not numeric");
+    /// ```
+    pub fn describe(&self, text: &PortText) -> String {
+        match *self {
+            PortErrorReason::OutOfRange(u64::MAX) if text.is_empty() => "port is out of range".to_string(),
+            PortErrorReason::OutOfRange(u64::MAX) => format!("port of {} digits is out of range", text.len()),
+            PortErrorReason::OutOfRange(port) => format!("port `{}` is out of range", port),
+            PortErrorReason::NotNumeric => match text.offending_byte() {
+                Option::Some(byte) => format!("port is not numeric, `{}` is not a digit", ascii::escape_default(byte)),
+                Option::None => "port is not numeric".to_string(),
+            },
+            PortErrorReason::Empty => "port is empty, nothing follows the `:`".to_string(),
+        }
     }
+}
+impl fmt::Display for PortErrorReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.describe(&PortText::default()))
+    }
+}
+
+/// A summary of a rejected port, as it appeared in the input
+///
+/// Only the length of the text and its first byte which is not an
+/// ASCII digit are kept, so `UrlFault` stays `Copy` and small.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub struct PortText {
+    len: u32,
+    offending: Option<u8>,
+}
+impl PortText {
+    /// `new` summarizes `text`
+    pub fn new(text: &str) -> PortText {
+        PortText {
+            len: text.len().min(u32::MAX as usize) as u32,
+            offending: text.bytes().find(|b| !b.is_ascii_digit()),
+        }
+    }
+
+    /// `len` returns the length in bytes of the text
+    pub fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    /// `is_empty` checks if the text was empty, or unknown
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// `offending_byte` returns the first byte of the text which is
+    /// not an ASCII digit
+    pub fn offending_byte(&self) -> Option<u8> {
+        self.offending
+    }
+}
+
+/// `port_text` finds the text after the `:` of the authority of
+/// `input`, without validating anything else.
+fn port_text(input: &str) -> Option<&str> {
+    let input = input.trim_matches(|c: char| c <= ' ');
+    let rest = &input[input.find(':')? + 1..];
+    let rest = rest.trim_start_matches(['/', '\\']);
+    let authority = &rest[..rest.find(['/', '\\', '?', '#']).unwrap_or(rest.len())];
+    let host_and_port = &authority[authority.rfind('@').map(|at| at + 1).unwrap_or(0)..];
+    let after_host = if host_and_port.starts_with('[') {
+        &host_and_port[host_and_port.find(']')? + 1..]
+    } else {
+        &host_and_port[host_and_port.find(':')?..]
+    };
+    after_host.strip_prefix(':')
+}
+
+impl fmt::Display for UrlFault {
+    fn fmt(&self,f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            UrlFault::Overflow { input_len } => {
+                write!(f, "Overflow {} (input was {} bytes)", self.message(), input_len)
+            }
+            UrlFault::InvalidPort { reason, ref text } => {
+                write!(f, "InvalidPort {}, {}", self.message(), reason.describe(text))
+            }
+            _ => write!(f, "{:?} {}", self, self.message()),
+        }
+    }
+}
+impl Error for UrlFault {
+    /// faults which were uplifted from the url parser report the
+    /// original `url::ParseError` as their source.
     fn source(&self) -> Option<&(dyn Error + 'static)> {
-        None
+        static EMPTY_HOST: url::ParseError = url::ParseError::EmptyHost;
+        static IDNA_ERROR: url::ParseError = url::ParseError::IdnaError;
+        static INVALID_PORT: url::ParseError = url::ParseError::InvalidPort;
+        static INVALID_IPV4: url::ParseError = url::ParseError::InvalidIpv4Address;
+        static INVALID_IPV6: url::ParseError = url::ParseError::InvalidIpv6Address;
+        static INVALID_DOMAIN: url::ParseError = url::ParseError::InvalidDomainCharacter;
+        static RELATIVE: url::ParseError = url::ParseError::RelativeUrlWithoutBase;
+        static CANNOT_BE_A_BASE: url::ParseError = url::ParseError::RelativeUrlWithCannotBeABaseBase;
+        static SET_HOST: url::ParseError = url::ParseError::SetHostOnCannotBeABaseUrl;
+        static OVERFLOW: url::ParseError = url::ParseError::Overflow;
+
+        let err: &'static url::ParseError = match self.parse_error()? {
+            url::ParseError::EmptyHost => &EMPTY_HOST,
+            url::ParseError::IdnaError => &IDNA_ERROR,
+            url::ParseError::InvalidPort => &INVALID_PORT,
+            url::ParseError::InvalidIpv4Address => &INVALID_IPV4,
+            url::ParseError::InvalidIpv6Address => &INVALID_IPV6,
+            url::ParseError::InvalidDomainCharacter => &INVALID_DOMAIN,
+            url::ParseError::RelativeUrlWithoutBase => &RELATIVE,
+            url::ParseError::RelativeUrlWithCannotBeABaseBase => &CANNOT_BE_A_BASE,
+            url::ParseError::SetHostOnCannotBeABaseUrl => &SET_HOST,
+            url::ParseError::Overflow => &OVERFLOW,
+        };
+        Some(err)
+    }
+}
+impl From<UrlFault> for io::Error {
+    fn from(err: UrlFault) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidInput, err)
     }
 }
 impl From<url::ParseError> for UrlFault {
@@ -70,7 +388,9 @@ impl From<url::ParseError> for UrlFault {
         match err {
             url::ParseError::EmptyHost => UrlFault::EmptyHost,
             url::ParseError::IdnaError => UrlFault::IdnaError,
-            url::ParseError::InvalidPort => UrlFault::InvalidPort,
+            url::ParseError::InvalidPort => {
+                UrlFault::InvalidPort { reason: PortErrorReason::NotNumeric, text: PortText::default() }
+            }
             url::ParseError::InvalidIpv4Address => UrlFault::InvalidIpv4Address,
             url::ParseError::InvalidIpv6Address => UrlFault::InvalidIpv6Address,
             url::ParseError::InvalidDomainCharacter => UrlFault::InvalidDomainCharacter,
@@ -79,7 +399,44 @@ impl From<url::ParseError> for UrlFault {
                 UrlFault::RelativeUrlWithCannotBeABaseUrlIsABaseUrl
             }
             url::ParseError::SetHostOnCannotBeABaseUrl => UrlFault::SetHostOnCannotBeABaseUrl,
-            url::ParseError::Overflow => UrlFault::Overflow,
+            url::ParseError::Overflow => UrlFault::Overflow { input_len: 0 },
+        }
+    }
+}
+
+/// Why `Url::from_env_var` did not return a `Url`
+///
+/// The missing variants hold the name of the variable.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum EnvUrlError {
+    /// the variable is not set
+    NotSet(String),
+    /// the variable is set, but is not valid unicode
+    NotUnicode(String),
+    /// the variable is set, but is not a valid URL
+    Parse(UrlFault),
+}
+impl From<UrlFault> for EnvUrlError {
+    fn from(fault: UrlFault) -> EnvUrlError {
+        EnvUrlError::Parse(fault)
+    }
+}
+impl fmt::Display for EnvUrlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            EnvUrlError::NotSet(ref var) => write!(f, "environment variable `{}` is not set", var),
+            EnvUrlError::NotUnicode(ref var) => {
+                write!(f, "environment variable `{}` is not valid unicode", var)
+            }
+            EnvUrlError::Parse(ref fault) => write!(f, "{}", fault),
+        }
+    }
+}
+impl Error for EnvUrlError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            EnvUrlError::Parse(ref fault) => Some(fault),
+            _ => None,
         }
     }
 }
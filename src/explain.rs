@@ -0,0 +1,204 @@
+use std::fmt;
+
+use super::span::{self, Component, Spans};
+use super::url;
+use super::{Host, Url, TRACKING_PARAMS};
+
+/// Queries longer than this many bytes are reported
+const LONG_QUERY: usize = 1024;
+
+/// How much a `Finding` matters
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Severity {
+    /// worth knowing, not suspicious on its own
+    Info,
+    /// often a sign of a mistake, or of a deceptive URL
+    Warning,
+}
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Severity::Info => f.write_str("info"),
+            Severity::Warning => f.write_str("warning"),
+        }
+    }
+}
+
+/// Something notable about one component of a URL, see `Url::explain`
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Finding {
+    pub severity: Severity,
+    pub component: Component,
+    pub message: String,
+}
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} [{}]: {}", self.severity, label(self.component), self.message)
+    }
+}
+
+/// An annotated breakdown of a URL, see `Url::explain`
+///
+/// `Display` writes one line per component (the password is never
+/// shown), then one line per finding. The format is stable, changes
+/// to it are breaking changes.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Explanation {
+    components: Vec<(Component, String)>,
+    query_pairs: Vec<(String, Option<String>)>,
+    findings: Vec<Finding>,
+}
+impl Explanation {
+    /// `components` are the components present, in order, each with
+    /// the text shown for it.
+    pub fn components<'a>(&'a self) -> &'a [(Component, String)] {
+        &self.components
+    }
+
+    /// `findings` are ordered by component
+    pub fn findings<'a>(&'a self) -> &'a [Finding] {
+        &self.findings
+    }
+
+    /// `has_warnings` checks if any finding is a `Severity::Warning`
+    pub fn has_warnings(&self) -> bool {
+        self.findings.iter().any(|finding| finding.severity == Severity::Warning)
+    }
+}
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for &(component, ref text) in self.components.iter() {
+            writeln!(f, "{:<10}{}", format!("{}:", label(component)), text)?;
+            if component == Component::Query {
+                for (key, value) in self.query_pairs.iter() {
+                    match *value {
+                        Option::Some(ref value) => writeln!(f, "  {} = {}", key, value)?,
+                        Option::None => writeln!(f, "  {}", key)?,
+                    }
+                }
+            }
+        }
+        if self.findings.is_empty() {
+            return writeln!(f, "findings: none");
+        }
+        writeln!(f, "findings:")?;
+        for finding in self.findings.iter() {
+            writeln!(f, "  {}", finding)?;
+        }
+        Ok(())
+    }
+}
+
+fn label(component: Component) -> &'static str {
+    match component {
+        Component::Scheme => "scheme",
+        Component::Username => "username",
+        Component::Password => "password",
+        Component::Host => "host",
+        Component::Port => "port",
+        Component::Path => "path",
+        Component::Query => "query",
+        Component::Fragment => "fragment",
+    }
+}
+
+/// `explain` builds the `Explanation` of `url`
+pub fn explain(url: &Url) -> Explanation {
+    let url_data = url.data.get_url_data();
+    let input = url.get_parsed_input();
+    let spans = Spans::scan(input);
+    let mut components = Vec::new();
+    let mut findings = Vec::new();
+    let mut find = |severity, component, message: String| {
+        findings.push(Finding { severity, component, message });
+    };
+
+    components.push((Component::Scheme, url.get_scheme().to_string()));
+
+    if let Option::Some(username) = url.get_username() {
+        components.push((Component::Username, username.to_string()));
+    }
+    if url_data.password().is_some() {
+        components.push((Component::Password, "(hidden)".to_string()));
+    }
+    if url.get_username().is_some() || url_data.password().is_some() {
+        find(Severity::Warning, Component::Username,
+             "URL contains credentials, which leak into logs and can disguise the host".to_string());
+    }
+
+    if let Option::Some(host) = url_data.host_str() {
+        let is_idn = host.split('.').any(|label| label.starts_with("xn--"));
+        if is_idn {
+            let (unicode, _) = url::idna::domain_to_unicode(host);
+            components.push((Component::Host, format!("{} ({})", host, unicode)));
+            find(Severity::Warning, Component::Host,
+                 format!("internationalized domain, displayed as `{}`", unicode));
+        } else if !host.is_empty() {
+            components.push((Component::Host, host.to_string()));
+        }
+        let is_ip = matches!(url.get_host(), Option::Some(Host::Ipv4(_)) | Option::Some(Host::Ipv6(_)));
+        let written = spans.get(Component::Host).map(|range| &input[range]);
+        match written {
+            Option::Some(written) if is_ip && !written.eq_ignore_ascii_case(host) => {
+                find(Severity::Warning, Component::Host,
+                     format!("IP address written as `{}` in the input", written));
+            }
+            _ => {}
+        }
+    }
+
+    if let Option::Some(port) = url.get_port() {
+        components.push((Component::Port, port.to_string()));
+    }
+
+    let raw = url_data.path();
+    if !raw.is_empty() {
+        let decoded = url.get_path_str().unwrap_or("");
+        if decoded == raw {
+            components.push((Component::Path, raw.to_string()));
+        } else {
+            components.push((Component::Path, format!("{} (written `{}`)", decoded, raw)));
+        }
+    }
+    let written_path = spans.get(Component::Path).map(|range| &input[range]).unwrap_or("");
+    let special = span::is_special(url.get_scheme());
+    let has_dot_segment = written_path
+        .split(|c| c == '/' || (special && c == '\\'))
+        .any(|segment| {
+            matches!(segment.to_ascii_lowercase().as_str(), "." | "%2e" | ".." | ".%2e" | "%2e." | "%2e%2e")
+        });
+    if has_dot_segment {
+        find(Severity::Warning, Component::Path,
+             format!("dot segments in `{}` were resolved", written_path));
+    }
+
+    let mut query_pairs = Vec::new();
+    if let Option::Some(query) = url_data.query() {
+        components.push((Component::Query, query.to_string()));
+        query_pairs = url
+            .query_iter()
+            .map(|(key, value)| (key.to_string(), value.map(str::to_string)))
+            .collect();
+        if query.len() > LONG_QUERY {
+            find(Severity::Warning, Component::Query,
+                 format!("query is unusually long ({} bytes)", query.len()));
+        }
+        let mut tracking: Vec<&str> = Vec::new();
+        for (key, _) in query_pairs.iter() {
+            let is_tracking = key.starts_with("utm_") || TRACKING_PARAMS.contains(&key.as_str());
+            if is_tracking && !tracking.contains(&key.as_str()) {
+                tracking.push(key);
+            }
+        }
+        if !tracking.is_empty() {
+            find(Severity::Info, Component::Query,
+                 format!("tracking parameters: {}", tracking.join(", ")));
+        }
+    }
+
+    if let Option::Some(fragment) = url_data.fragment() {
+        components.push((Component::Fragment, fragment.to_string()));
+    }
+
+    Explanation { components, query_pairs, findings }
+}
@@ -0,0 +1,64 @@
+use std::borrow::Cow;
+
+use super::internal;
+
+/// Owned, decoded, `application/x-www-form-urlencoded` pairs
+///
+/// Returned by `parse_form_body`. Pairs keep their order, `key=` is
+/// `Some("")` while a bare `key` is `None`.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct QueryMap {
+    pairs: Vec<(String, Option<String>)>,
+}
+impl QueryMap {
+    /// `iter` returns the pairs in the order they occurred
+    pub fn iter<'a>(&'a self) -> impl Iterator<Item = (&'a str, Option<&'a str>)> + 'a {
+        self.pairs
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_ref().map(String::as_str)))
+    }
+
+    /// `get_first_value_for` returns the value of the first pair
+    /// with this key. The outer `Option` reports if the key exists.
+    pub fn get_first_value_for<'a>(&'a self, key: &str) -> Option<Option<&'a str>> {
+        self.iter()
+            .filter(|&(k, _)| k == key)
+            .map(|(_, value)| value)
+            .next()
+    }
+
+    /// `len` returns the number of pairs
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    /// `is_empty` checks if there are no pairs
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    /// `to_form_string` encodes the pairs again, see
+    /// `QueryData::to_form_string`.
+    pub fn to_form_string(&self) -> String {
+        internal::to_form_string(self.iter())
+    }
+}
+
+/// `parse_form_body` decodes an `application/x-www-form-urlencoded`
+/// body, with the same rules used for URL queries. This is the
+/// inverse of `QueryData::to_form_string`.
+///
+/// ```
+/// use serde_url::{Url, parse_form_body};
+///
+/// let url = Url::new(&"https://google.com/?q=a%20b&flag&empty=").unwrap();
+/// let body = url.get_query_data().unwrap().to_form_string();
+/// let form = parse_form_body(&body);
+/// assert_eq!(form.iter().collect::<Vec<_>>(), vec![("q", Some("a b")), ("flag", None), ("empty", Some(""))]);
+/// ```
+pub fn parse_form_body(body: &str) -> QueryMap {
+    let pairs = internal::form_pairs(body)
+        .map(|(key, value)| (key.into_owned(), value.map(Cow::into_owned)))
+        .collect();
+    QueryMap { pairs }
+}
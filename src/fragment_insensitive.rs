@@ -0,0 +1,93 @@
+use std::{fmt, hash, ops};
+
+use super::Url;
+
+/// A borrowed `Url` which compares and hashes without its fragment
+///
+/// This lets `page#section1` and `page#section2` key the same
+/// entry of a `HashMap` without building new URLs.
+///
+/// ```
+/// use std::collections::HashMap;
+/// use serde_url::{Url, FragmentInsensitive};
+///
+/// let a = Url::new(&"https://google.com/page#section1").unwrap();
+/// let b = Url::new(&"https://google.com/page#section2").unwrap();
+///
+/// let mut views = HashMap::new();
+/// *views.entry(FragmentInsensitive(&a)).or_insert(0) += 1;
+/// *views.entry(FragmentInsensitive(&b)).or_insert(0) += 1;
+/// assert_eq!(views.len(), 1);
+/// assert_eq!(views[&FragmentInsensitive(&a)], 2);
+/// ```
+#[derive(Clone, Copy)]
+pub struct FragmentInsensitive<'a>(pub &'a Url);
+impl<'a> hash::Hash for FragmentInsensitive<'a> {
+    #[inline(always)]
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: hash::Hasher,
+    {
+        self.0.get_string_without_fragment().hash(state)
+    }
+}
+impl<'a> PartialEq for FragmentInsensitive<'a> {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_ignore_fragment(other.0)
+    }
+}
+impl<'a> Eq for FragmentInsensitive<'a> {}
+impl<'a> ops::Deref for FragmentInsensitive<'a> {
+    type Target = Url;
+    fn deref(&self) -> &Url {
+        self.0
+    }
+}
+impl<'a> fmt::Debug for FragmentInsensitive<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0.get_string())
+    }
+}
+
+/// An owned `Url` which compares and hashes without its fragment
+#[derive(Clone)]
+pub struct FragmentInsensitiveUrl(pub Url);
+impl FragmentInsensitiveUrl {
+    /// `into_url` returns the wrapped `Url`, fragment included
+    pub fn into_url(self) -> Url {
+        self.0
+    }
+}
+impl From<Url> for FragmentInsensitiveUrl {
+    fn from(url: Url) -> FragmentInsensitiveUrl {
+        FragmentInsensitiveUrl(url)
+    }
+}
+impl hash::Hash for FragmentInsensitiveUrl {
+    #[inline(always)]
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: hash::Hasher,
+    {
+        FragmentInsensitive(&self.0).hash(state)
+    }
+}
+impl PartialEq for FragmentInsensitiveUrl {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_ignore_fragment(&other.0)
+    }
+}
+impl Eq for FragmentInsensitiveUrl {}
+impl ops::Deref for FragmentInsensitiveUrl {
+    type Target = Url;
+    fn deref(&self) -> &Url {
+        &self.0
+    }
+}
+impl fmt::Debug for FragmentInsensitiveUrl {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0.get_string())
+    }
+}
@@ -2,12 +2,19 @@
 use std::path::Path;
 use std::net::{Ipv4Addr, Ipv6Addr, IpAddr, SocketAddr};
 use std::fmt::{self, Debug, Display};
+use std::borrow::Cow;
+use std::num::ParseIntError;
 use std::hash::{Hash, Hasher};
+use std::sync::{Arc, OnceLock};
+use std::collections::HashMap;
 
 use super::errors::UrlFault;
+use super::VERSION_PARAMS;
 
 use super::url;
+use super::serde;
 use super::url::percent_encoding::percent_decode;
+use super::url::Position;
 
 /// PrivateUrl is a structure which constains the expanded
 /// data of a parsed URL
@@ -15,17 +22,22 @@ pub struct PrivateUrl {
     url_data: url::Url,
     string_data: Box<str>,
     input_data: Box<str>,
+    recorded_input: Option<Box<str>>,
     username: Option<Box<str>>,
     password: Option<Box<str>>,
     path: Option<Box<str>>,
     full_query: Option<Box<str>>,
     query_key_values: Box<[(Box<str>, Option<Box<str>>)]>,
+    full_domain: Option<Box<str>>,
+    shared_string: OnceLock<Arc<str>>,
+    hash_bases: OnceLock<(u64, u64)>,
 }
 impl PrivateUrl {
     /// `new` handles parsing a URL input
     pub fn new(input: &str) -> Result<PrivateUrl, UrlFault> {
         let input_data = input.to_string().into_boxed_str();
-        let url_data = url::Url::parse(input)?;
+        let url_data = url::Url::parse(input)
+            .map_err(|err| UrlFault::from_parse_error(err, input))?;
         let string_data = url_data.to_string().into_boxed_str();
         let username = match boilerplate(url_data.username(), UrlFault::UserNameUtf8) {
             Option::None => None,
@@ -47,41 +59,120 @@ impl PrivateUrl {
             Option::Some(Ok(path)) => Some(path),
             Option::Some(Err(e)) => return Err(e),
         };
-        let query_key_values = url_data
-            .query_pairs()
+        let query_key_values = form_pairs(url_data.query().unwrap_or(""))
             .map(|(key, value)| -> (Box<str>, Option<Box<str>>) {
-                let value: Option<Box<str>> = if value.len() > 0 {
-                    Some(value.to_string().into_boxed_str())
-                } else {
-                    None
-                };
-                let key = key.to_string().into_boxed_str();
-                (key, value)
+                let value = value.map(|value| value.into_owned().into_boxed_str());
+                (key.into_owned().into_boxed_str(), value)
             })
             .collect::<Vec<(Box<str>, Option<Box<str>>)>>()
             .into_boxed_slice();
+        let full_domain = url_data.host_str().map(|host| {
+            match url_data.port() {
+                Option::Some(port) => format!("{}:{}", host, port).into_boxed_str(),
+                Option::None => host.to_string().into_boxed_str(),
+            }
+        });
 
         Ok(PrivateUrl {
             url_data,
             input_data,
+            recorded_input: None,
             string_data,
             username,
             password,
             path,
             full_query,
             query_key_values,
+            full_domain,
+            shared_string: OnceLock::new(),
+            hash_bases: OnceLock::new(),
         })
     }
 
+    /// `with_input` copies the parsed state, recording `input` as a
+    /// label returned by `get_input`. Nothing is reparsed, and the
+    /// input which was parsed is kept alongside it.
+    pub fn with_input(&self, input: &str) -> PrivateUrl {
+        PrivateUrl {
+            url_data: self.url_data.clone(),
+            input_data: self.input_data.clone(),
+            recorded_input: Some(input.to_string().into_boxed_str()),
+            string_data: self.string_data.clone(),
+            username: self.username.clone(),
+            password: self.password.clone(),
+            path: self.path.clone(),
+            full_query: self.full_query.clone(),
+            query_key_values: self.query_key_values.clone(),
+            full_domain: self.full_domain.clone(),
+            shared_string: OnceLock::new(),
+            hash_bases: self.hash_bases.clone(),
+        }
+    }
+
+    /// `get_url_data` returns the underlying parsed URL
+    #[inline(always)]
+    pub fn get_url_data<'a>(&'a self) -> &'a url::Url {
+        &self.url_data
+    }
+
     /// `get_string` just returns a string
     #[inline(always)]
     pub fn get_string<'a>(&'a self) -> &'a str {
         self.string_data.as_ref()
     }
 
-    /// `get_input` just returns the orginal input string
+    /// `into_string_data` returns the normalized string, without
+    /// copying it.
+    #[inline(always)]
+    pub fn into_string_data(self) -> Box<str> {
+        self.string_data
+    }
+
+    /// `get_display` borrows the normalized string for `Display`
+    #[inline(always)]
+    pub fn get_display<'a>(&'a self) -> UrlDisplay<'a> {
+        UrlDisplay { string: self.get_string() }
+    }
+
+    /// `get_display_from_path` borrows the normalized string, from
+    /// the start of the path onwards, for `Display`
+    #[inline(always)]
+    pub fn get_display_from_path<'a>(&'a self) -> UrlDisplay<'a> {
+        UrlDisplay { string: &self.url_data[Position::BeforePath..] }
+    }
+
+    /// `get_shared_string` returns the normalized string as an
+    /// `Arc<str>`. This is allocated once, the first time it is
+    /// requested, every call afterwards shares that allocation.
+    #[inline(always)]
+    pub fn get_shared_string(&self) -> Arc<str> {
+        self.shared_string
+            .get_or_init(|| Arc::from(self.get_string()))
+            .clone()
+    }
+
+    /// `get_hash_bases` returns two independent 64bit hashes of the
+    /// normalized string, an FNV-1a hash and an odd FxHash style hash.
+    /// Both are computed in one pass, the first time they are requested.
+    #[inline(always)]
+    pub fn get_hash_bases(&self) -> (u64, u64) {
+        *self.hash_bases.get_or_init(|| hash_bases(self.get_string()))
+    }
+
+    /// `get_input` just returns the orginal input string, or the
+    /// label recorded by `with_input`
     #[inline(always)]
     pub fn get_input<'a>(&'a self) -> &'a str {
+        match self.recorded_input {
+            Option::Some(ref recorded) => recorded.as_ref(),
+            Option::None => self.input_data.as_ref(),
+        }
+    }
+
+    /// `get_parsed_input` returns the input which was parsed, a
+    /// recorded label does not replace it
+    #[inline(always)]
+    pub fn get_parsed_input<'a>(&'a self) -> &'a str {
         self.input_data.as_ref()
     }
 
@@ -124,9 +215,9 @@ impl PrivateUrl {
     #[inline(always)]
     pub fn get_host<'a>(&'a self) -> Option<Host<&'a str>> {
         match self.url_data.host() {
-            Option::Some(url::Host::Ipv4(ref arg)) => Some(Host::Ipv4(arg.clone())),
-            Option::Some(url::Host::Ipv6(ref arg)) => Some(Host::Ipv6(arg.clone())),
-            Option::Some(url::Host::Domain(ref arg)) => Some(Host::Domain(arg.clone())),
+            Option::Some(url::Host::Ipv4(arg)) => Some(Host::Ipv4(arg)),
+            Option::Some(url::Host::Ipv6(arg)) => Some(Host::Ipv6(arg)),
+            Option::Some(url::Host::Domain(arg)) => Some(Host::Domain(arg)),
             _ => None,
         }
     }
@@ -137,6 +228,13 @@ impl PrivateUrl {
         self.url_data.port()
     }
 
+    /// `get_full_domain` returns the host, and explicit port, as
+    /// one `host:port` string.
+    #[inline(always)]
+    pub fn get_full_domain<'a>(&'a self) -> Option<&'a str> {
+        self.full_domain.as_ref().map(|full_domain| full_domain.as_ref())
+    }
+
     /// `get_origin` returns an a _non-opaque_ origin. If one
     /// is present. This contains the `host` and `port`, as
     /// well as `scheme` information.
@@ -154,6 +252,83 @@ impl PrivateUrl {
             .next()
     }
 
+    /// `same_origin` checks if two URLs share a tuple origin,
+    /// default ports are taken into account.
+    #[inline(always)]
+    pub fn same_origin(&self, other: &PrivateUrl) -> bool {
+        self.url_data.origin() == other.url_data.origin()
+    }
+
+    /// `has_tuple_origin` checks the origin is not opaque.
+    #[inline(always)]
+    pub fn has_tuple_origin(&self) -> bool {
+        self.url_data.origin().is_tuple()
+    }
+
+    /// `get_origin_serialization` returns the origin as it would
+    /// appear in an `Origin` header. Opaque origins are `null`.
+    #[inline(always)]
+    pub fn get_origin_serialization(&self) -> String {
+        self.url_data.origin().ascii_serialization()
+    }
+
+    /// `get_scheme_authority_path_parts` returns the normalized string
+    /// up to the end of the path, with the password removed. This is
+    /// done in three pieces so it can be compared without allocating.
+    #[inline(always)]
+    pub fn get_scheme_authority_path_parts<'a>(&'a self) -> (&'a str, &'a str, &'a str) {
+        let url = &self.url_data;
+        let at = if url.username().is_empty() { "" } else { "@" };
+        (
+            &url[..Position::AfterUsername],
+            at,
+            &url[Position::BeforeHost..Position::AfterPath],
+        )
+    }
+
+    /// `split_after_host` splits the normalized string where an
+    /// explicit port would begin.
+    #[inline(always)]
+    pub fn split_after_host<'a>(&'a self) -> (&'a str, &'a str) {
+        let url = &self.url_data;
+        (&url[..Position::AfterHost], &url[Position::AfterHost..])
+    }
+
+    /// `split_at_path` splits the normalized string where the path
+    /// begins.
+    #[inline(always)]
+    pub fn split_at_path<'a>(&'a self) -> (&'a str, &'a str) {
+        let url = &self.url_data;
+        (&url[..Position::BeforePath], &url[Position::BeforePath..])
+    }
+
+    /// `get_string_before_query` returns the normalized string up
+    /// to the end of the path.
+    #[inline(always)]
+    pub fn get_string_before_query<'a>(&'a self) -> &'a str {
+        &self.url_data[..Position::AfterPath]
+    }
+
+    /// `get_path_and_query` returns the normalized path and query.
+    #[inline(always)]
+    pub fn get_path_and_query<'a>(&'a self) -> &'a str {
+        &self.url_data[Position::BeforePath..Position::AfterQuery]
+    }
+
+    /// `get_string_without_fragment` returns the normalized string
+    /// up to, but not including, the `#`.
+    #[inline(always)]
+    pub fn get_string_without_fragment<'a>(&'a self) -> &'a str {
+        &self.url_data[..Position::AfterQuery]
+    }
+
+    /// `get_fragment` returns the `fragment` component of the URL
+    /// as it appears in the normalized string.
+    #[inline(always)]
+    pub fn get_fragment<'a>(&'a self) -> Option<&'a str> {
+        self.url_data.fragment()
+    }
+
     /// `get_path` returns the `path` component of the URL
     #[inline(always)]
     pub fn get_path<'a>(&'a self) -> Option<&'a Path> {
@@ -170,13 +345,20 @@ impl PrivateUrl {
     /// `get_query_info` returns information about query parameters
     #[inline(always)]
     pub fn get_query_info<'a>(&'a self) -> Option<QueryData<'a>> {
-        match self.full_query {
-            Option::None => None,
-            Option::Some(ref query) => Some(QueryData {
-                full_query: query.as_ref(),
-                collection: &self.query_key_values,
-            }),
-        }
+        self.full_query.as_ref().map(|query| QueryData {
+            full_query: query.as_ref(),
+            raw_query: self.url_data.query().unwrap_or(""),
+            collection: &self.query_key_values,
+        })
+    }
+
+    /// `query_iter` returns the decoded query key value pairs in
+    /// the order they occur.
+    #[inline(always)]
+    pub fn query_iter<'a>(&'a self) -> impl Iterator<Item = (&'a str, Option<&'a str>)> + 'a {
+        self.query_key_values
+            .iter()
+            .map(|(key, value)| (key.as_ref(), borrow_checker(value)))
     }
 }
 
@@ -189,16 +371,107 @@ impl PrivateUrl {
 /// So instead of sanely supporting whatever `go-lang` does, we just offer
 /// a handful of options. Worse case scenario define your own wrapper
 /// type its zero cost abstraction.
+///
+/// # Note `+` handling
+///
+/// Most methods here (`iter`, `key_exists`, `get_first_value_for`,
+/// `get_all_values`, and the `comma_split` family) follow the
+/// `application/x-www-form-urlencoded` convention, which decodes a
+/// `+` into a space. Plenty of APIs use `+` literally, so
+/// `get_key_strict` only performs percent decoding and leaves `+`
+/// alone.
 pub struct QueryData<'a> {
     full_query: &'a str,
+    raw_query: &'a str,
     collection: &'a [(Box<str>, Option<Box<str>>)],
 }
 impl<'a> QueryData<'a> {
     /// `get_full_query` attempts to return the percentage decoded query string
-    pub fn get_full_query<'b>(&'b self) -> &'b str {
+    pub fn get_full_query(&self) -> &'a str {
         self.full_query
     }
 
+    /// `to_form_string` re-encodes the query as an
+    /// `application/x-www-form-urlencoded` body, spaces become `+`
+    /// and `&`, `=`, and `+` within keys or values are escaped.
+    /// Pairs keep their order, and `key=` stays distinct from `key`.
+    ///
+    /// ```
+    /// use serde_url::Url;
+    ///
+    /// let data = "https://google.com/?q=a%20b&op=1%2B1&flag&empty=";
+    /// let url = Url::new(&data).unwrap();
+    /// let query_data = url.get_query_data().unwrap();
+    /// assert_eq!(query_data.to_form_string(), "q=a+b&op=1%2B1&flag&empty=");
+    /// ```
+    pub fn to_form_string(&self) -> String {
+        to_form_string(form_pairs(self.raw_query))
+    }
+
+    /// iterates over every key value pair in the order they
+    /// occur within the query.
+    ///
+    /// ```
+    /// use serde_url::Url;
+    ///
+    /// let data = "https://google.com/?foo=bar&baz";
+    /// let url = Url::new(&data).unwrap();
+    /// let query_data = url.get_query_data().unwrap();
+    /// let pairs = query_data.iter().collect::<Vec<_>>();
+    /// assert_eq!(pairs, vec![("foo", Some("bar")), ("baz", None)]);
+    /// ```
+    pub fn iter<'b>(&'b self) -> impl Iterator<Item = (&'b str, Option<&'b str>)> + 'b {
+        self.collection
+            .iter()
+            .map(|(key, value)| (key.as_ref(), borrow_checker(value)))
+    }
+
+    /// `grouped` yields each distinct key once, in the order keys
+    /// first appear, with all of its values in the order they appear.
+    /// Keys without a value contribute `None`.
+    ///
+    /// ```
+    /// use serde_url::Url;
+    ///
+    /// let url = Url::new(&"https://google.com/?a=1&b=2&a=3&b").unwrap();
+    /// let query_data = url.get_query_data().unwrap();
+    /// let groups = query_data.grouped().collect::<Vec<_>>();
+    /// assert_eq!(groups, vec![
+    ///     ("a", vec![Some("1"), Some("3")]),
+    ///     ("b", vec![Some("2"), None]),
+    /// ]);
+    /// ```
+    pub fn grouped<'b>(&'b self) -> impl Iterator<Item = (&'b str, Vec<Option<&'b str>>)> + 'b {
+        let mut index: HashMap<&'b str, usize> = HashMap::new();
+        let mut groups: Vec<(&'b str, Vec<Option<&'b str>>)> = Vec::new();
+        for (key, value) in self.iter() {
+            match index.get(key) {
+                Option::Some(&at) => groups[at].1.push(value),
+                Option::None => {
+                    index.insert(key, groups.len());
+                    groups.push((key, vec![value]));
+                }
+            }
+        }
+        groups.into_iter()
+    }
+
+    /// `get_all` yields every value of `key`, in the order they
+    /// appear. Occurrences without a value are `None`.
+    ///
+    /// ```
+    /// use serde_url::Url;
+    ///
+    /// let url = Url::new(&"https://google.com/?tag=a&x=1&tag&tag=b").unwrap();
+    /// let query_data = url.get_query_data().unwrap();
+    /// assert_eq!(query_data.get_all("tag").collect::<Vec<_>>(), vec![Some("a"), None, Some("b")]);
+    /// ```
+    pub fn get_all<'b>(&'b self, key: &'b str) -> impl Iterator<Item = Option<&'b str>> + 'b {
+        self.iter()
+            .filter(move |&(existing, _)| existing == key)
+            .map(|(_, value)| value)
+    }
+
     /// checks if a query value exists. Does not check if the value
     /// exists.
     ///
@@ -237,7 +510,7 @@ impl<'a> QueryData<'a> {
     /// ```
     /// use serde_url::Url;
     ///
-    /// let data = "https://google.com/?bar=&bar=foo";
+    /// let data = "https://google.com/?bar&bar=foo";
     /// let url = Url::new(&data).unwrap();
     /// let query_data = url.get_query_data().unwrap();
     /// assert!(query_data.get_first_value_for(&"bar").unwrap() == "foo");
@@ -264,6 +537,115 @@ impl<'a> QueryData<'a> {
         )
     }
 
+    /// `get_key_ignore_case` looks a key up ignoring ASCII case, for
+    /// upstreams which are inconsistent about it. Non-ASCII letters
+    /// must match exactly, no Unicode case folding is done. When
+    /// several keys match the first in document order wins.
+    ///
+    /// The outer `Option` reports if the key exists, the inner
+    /// `Option` if it carried a value.
+    ///
+    /// ```
+    /// use serde_url::Url;
+    ///
+    /// let url = Url::new(&"https://google.com/?PageSize=10&pagesize=20&Flag").unwrap();
+    /// let query_data = url.get_query_data().unwrap();
+    /// assert_eq!(query_data.get_key_ignore_case("pageSIZE"), Some(Some("10")));
+    /// assert_eq!(query_data.get_key_ignore_case("flag"), Some(None));
+    /// assert_eq!(query_data.get_key_ignore_case("page"), None);
+    /// ```
+    pub fn get_key_ignore_case(&self, search_term: &str) -> Option<Option<&'a str>> {
+        self.collection
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(search_term))
+            .map(|(_, value)| borrow_checker(value))
+    }
+
+    /// `keys_ignore_case_fold` iterates over every pair in document
+    /// order with its key ASCII lowercased, see `get_key_ignore_case`.
+    ///
+    /// ```
+    /// use serde_url::Url;
+    ///
+    /// let url = Url::new(&"https://google.com/?PageSize=10&pagesize=20").unwrap();
+    /// let query_data = url.get_query_data().unwrap();
+    /// let pairs = query_data.keys_ignore_case_fold().collect::<Vec<_>>();
+    /// assert_eq!(pairs, vec![("pagesize".to_string(), Some("10")), ("pagesize".to_string(), Some("20"))]);
+    /// ```
+    pub fn keys_ignore_case_fold<'b>(&'b self) -> impl Iterator<Item = (String, Option<&'b str>)> + 'b {
+        self.iter()
+            .map(|(key, value)| (key.to_ascii_lowercase(), value))
+    }
+
+    /// `get_key_as_unix_timestamp` parses the first value of `key`
+    /// as an integer, such as the seconds in `?t=1699999999`. `None`
+    /// when the key does not exist. Only integers are understood, a
+    /// date such as `?t=2024-01-01` is an `Err`.
+    ///
+    /// ```
+    /// use serde_url::Url;
+    ///
+    /// let url = Url::new(&"https://google.com/app.js?t=1699999999&d=2024-01-01").unwrap();
+    /// let query_data = url.get_query_data().unwrap();
+    /// assert_eq!(query_data.get_key_as_unix_timestamp("t"), Some(Ok(1699999999)));
+    /// assert!(query_data.get_key_as_unix_timestamp("d").unwrap().is_err());
+    /// assert_eq!(query_data.get_key_as_unix_timestamp("v"), None);
+    /// ```
+    pub fn get_key_as_unix_timestamp(&self, search_term: &str) -> Option<Result<i64, ParseIntError>> {
+        self.iter()
+            .find(|&(key, _)| key == search_term)
+            .map(|(_, value)| value.unwrap_or("").parse::<i64>())
+    }
+
+    /// `find_version_param` looks for a cache busting parameter, the
+    /// keys in `VERSION_PARAMS` are tried in order and the first with
+    /// a value is returned as `(key, value)`.
+    ///
+    /// ```
+    /// use serde_url::Url;
+    ///
+    /// let url = Url::new(&"https://google.com/app.js?ts=1&v=2").unwrap();
+    /// let query_data = url.get_query_data().unwrap();
+    /// assert_eq!(query_data.find_version_param(), Some(("v", "2")));
+    /// ```
+    pub fn find_version_param<'b>(&'b self) -> Option<(&'b str, &'b str)> {
+        VERSION_PARAMS
+            .iter()
+            .filter_map(|candidate| {
+                self.iter()
+                    .filter(|&(key, _)| key == *candidate)
+                    .filter_map(|(key, value)| value.map(|value| (key, value)))
+                    .next()
+            })
+            .next()
+    }
+
+    /// Returns the first value for a key, performing only percent
+    /// decoding. Unlike `get_first_value_for` a `+` is _not_ treated
+    /// as a space, neither in the key nor the value.
+    ///
+    /// The outer `Option` reports if the key exists, the inner
+    /// `Option` if it carried a value.
+    ///
+    /// ```
+    /// use serde_url::Url;
+    ///
+    /// let data = "https://google.com/?key=a+b%20c";
+    /// let url = Url::new(&data).unwrap();
+    /// let query_data = url.get_query_data().unwrap();
+    /// assert!(query_data.get_first_value_for(&"key").unwrap() == "a b c");
+    /// assert!(query_data.get_key_strict(&"key").unwrap().unwrap() == "a+b c");
+    /// ```
+    pub fn get_key_strict<'b, S>(&'b self, search_term: &S) -> Option<Option<Cow<'b, str>>>
+    where
+        S: AsRef<str>,
+    {
+        raw_pairs(self.raw_query)
+            .filter(|(key, _)| -> bool { strict_decode(key).eq(search_term.as_ref()) })
+            .map(|(_, value)| value.filter(|value| !value.is_empty()).map(strict_decode))
+            .next()
+    }
+
     /// returns all values does not perform any splitting
     ///
     /// ```
@@ -344,6 +726,104 @@ impl<'a> QueryData<'a> {
     }
 }
 
+/// `raw_pairs` splits a raw query string on `&` and then on the
+/// first `=` of each pair. Nothing is decoded.
+pub fn raw_pairs<'a>(query: &'a str) -> impl Iterator<Item = (&'a str, Option<&'a str>)> + 'a {
+    query.split('&').map(|pair| {
+        let mut iter = pair.splitn(2, '=');
+        (iter.next().unwrap_or(""), iter.next())
+    })
+}
+
+/// `extension_dot` finds the `.` which starts the extension of a
+/// file name, a leading `.` does not count.
+pub fn extension_dot(file: &str) -> Option<usize> {
+    file.rfind('.').filter(|&dot| dot > 0)
+}
+
+/// `glob_match` matches `text` against a glob, `*` matches any run
+/// of bytes except `/`, and `**` (or more stars) any run at all.
+/// Everything else is literal. This is a dynamic program over the
+/// pattern, so it never backtracks, `O(pattern * text)` at worst.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let text = text.as_bytes();
+    let mut prev = vec![false; text.len() + 1];
+    prev[0] = true;
+    let mut pattern = pattern.as_bytes();
+    while !pattern.is_empty() {
+        let mut cur = vec![false; text.len() + 1];
+        if pattern.starts_with(b"**") {
+            cur[0] = prev[0];
+            for j in 1..=text.len() {
+                cur[j] = prev[j] || cur[j - 1];
+            }
+            while pattern.first() == Some(&b'*') {
+                pattern = &pattern[1..];
+            }
+        } else if pattern[0] == b'*' {
+            cur[0] = prev[0];
+            for j in 1..=text.len() {
+                cur[j] = prev[j] || (cur[j - 1] && text[j - 1] != b'/');
+            }
+            pattern = &pattern[1..];
+        } else {
+            for j in 0..text.len() {
+                cur[j + 1] = prev[j] && text[j] == pattern[0];
+            }
+            pattern = &pattern[1..];
+        }
+        prev = cur;
+    }
+    prev[text.len()]
+}
+
+/// `to_form_string` form encodes pairs, a `None` value is written
+/// as a bare key.
+pub fn to_form_string<K, V, I>(pairs: I) -> String
+where
+    K: AsRef<str>,
+    V: AsRef<str>,
+    I: IntoIterator<Item = (K, Option<V>)>,
+{
+    let encode = |arg: &str| url::form_urlencoded::byte_serialize(arg.as_bytes()).collect::<String>();
+    pairs
+        .into_iter()
+        .map(|(key, value)| {
+            match value {
+                Option::Some(value) => format!("{}={}", encode(key.as_ref()), encode(value.as_ref())),
+                Option::None => encode(key.as_ref()),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("&")
+}
+
+/// `form_pairs` splits a raw query, or form body, into decoded
+/// pairs following `application/x-www-form-urlencoded` rules. Empty
+/// pairs (from `&&`) are skipped, `key=` and `key` stay distinct,
+/// `Some("")` and `None`. This is the one parser behind the URL's
+/// query data and `parse_form_body`.
+pub fn form_pairs<'a>(query: &'a str) -> impl Iterator<Item = (Cow<'a, str>, Option<Cow<'a, str>>)> + 'a {
+    raw_pairs(query)
+        .filter(|&(key, value)| !key.is_empty() || value.is_some())
+        .map(|(key, value)| (form_decode(key), value.map(form_decode)))
+}
+
+/// `form_decode` turns `+` into a space, then percent decodes
+fn form_decode<'a>(arg: &'a str) -> Cow<'a, str> {
+    if arg.contains('+') {
+        Cow::Owned(strict_decode(&arg.replace('+', " ")).into_owned())
+    } else {
+        strict_decode(arg)
+    }
+}
+
+/// `strict_decode` percent decodes, but leaves `+` alone
+#[inline(always)]
+fn strict_decode<'a>(arg: &'a str) -> Cow<'a, str> {
+    percent_decode(arg.as_bytes()).decode_utf8_lossy()
+}
+
 fn collect_into<'a, I>(iter: I) -> Option<Box<[&'a str]>>
 where
     I: IntoIterator<Item = &'a str>,
@@ -390,6 +870,7 @@ impl<T: Clone> Clone for Host<T> {
         }
     }
 }
+impl<T: Copy> Copy for Host<T> {}
 impl<T: Hash> Hash for Host<T> {
     fn hash<H: Hasher>(&self, hasher: &mut H) {
         match self {
@@ -402,15 +883,149 @@ impl<T: Hash> Hash for Host<T> {
 impl<T: PartialEq> PartialEq for Host<T> {
     fn eq(&self, other: &Host<T>) -> bool {
         match (self, other) {
-            (&Host::Domain(ref this), &Host::Domain(ref that)) => this.eq(that),
-            (&Host::Ipv4(ref this), &Host::Ipv4(ref that)) => this.eq(that),
-            (&Host::Ipv6(ref this), &Host::Ipv6(ref that)) => this.eq(that),
+            (Host::Domain(this), Host::Domain(that)) => this.eq(that),
+            (Host::Ipv4(this), Host::Ipv4(that)) => this.eq(that),
+            (Host::Ipv6(this), Host::Ipv6(that)) => this.eq(that),
             _ => false,
         }
     }
 }
 impl<T: Eq> Eq for Host<T> {}
 
+/// Every component of a URL at once
+///
+/// Returned by `Url::components`, this is a borrowed snapshot
+/// which saves calling each getter in turn. The fields have the
+/// same meaning as their respective getters on `Url`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Components<'a> {
+    pub scheme: &'a str,
+    pub username: Option<&'a str>,
+    pub password: Option<&'a str>,
+    pub host: Option<Host<&'a str>>,
+    pub port: Option<u16>,
+    pub path: Option<&'a str>,
+    pub query: Option<&'a str>,
+    pub fragment: Option<&'a str>,
+}
+impl<'a> serde::Serialize for Components<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        // hosts are written as plain strings
+        let host = self.host.map(|host| match host {
+            Host::Domain(domain) => domain.to_string(),
+            Host::Ipv4(ipv4) => ipv4.to_string(),
+            Host::Ipv6(ipv6) => ipv6.to_string(),
+        });
+        let mut state = serializer.serialize_struct("Components", 8)?;
+        state.serialize_field("scheme", self.scheme)?;
+        state.serialize_field("username", &self.username)?;
+        state.serialize_field("password", &self.password)?;
+        state.serialize_field("host", &host)?;
+        state.serialize_field("port", &self.port)?;
+        state.serialize_field("path", &self.path)?;
+        state.serialize_field("query", &self.query)?;
+        state.serialize_field("fragment", &self.fragment)?;
+        state.end()
+    }
+}
+
+/// Every component of a URL, owned
+///
+/// Returned by `Url::into_owned_parts`, this is `Components` which
+/// outlives the `Url`. Hosts are written as plain strings, IPv6
+/// addresses without brackets, as `Components` serializes them.
+///
+/// `Components` is the borrowed form. A `Components<'static>` cannot
+/// be built from owned strings without leaking them, so there is no
+/// `From<UrlOwnedParts>` for it, borrow one with `as_components`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct UrlOwnedParts {
+    pub scheme: String,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub host: Option<String>,
+    pub port: Option<u16>,
+    pub path: Option<String>,
+    pub query: Option<String>,
+    pub fragment: Option<String>,
+}
+impl<'a> From<Components<'a>> for UrlOwnedParts {
+    fn from(parts: Components<'a>) -> UrlOwnedParts {
+        UrlOwnedParts {
+            scheme: parts.scheme.to_string(),
+            username: parts.username.map(str::to_string),
+            password: parts.password.map(str::to_string),
+            host: parts.host.map(|host| match host {
+                Host::Domain(domain) => domain.to_string(),
+                Host::Ipv4(ipv4) => ipv4.to_string(),
+                Host::Ipv6(ipv6) => ipv6.to_string(),
+            }),
+            port: parts.port,
+            path: parts.path.map(str::to_string),
+            query: parts.query.map(str::to_string),
+            fragment: parts.fragment.map(str::to_string),
+        }
+    }
+}
+impl UrlOwnedParts {
+    /// `as_components` borrows these parts as `Components`. Hosts which
+    /// are IP addresses become `Host::Ipv4` or `Host::Ipv6` again.
+    ///
+    /// ```
+    /// use serde_url::Url;
+    ///
+    /// let url = Url::new(&"https://[::1]:8080/a?q=1").unwrap();
+    /// let parts = url.clone().into_owned_parts();
+    /// assert_eq!(parts.as_components(), url.components());
+    /// ```
+    pub fn as_components<'a>(&'a self) -> Components<'a> {
+        Components {
+            scheme: &self.scheme,
+            username: self.username.as_deref(),
+            password: self.password.as_deref(),
+            host: self.host.as_ref().map(|host| {
+                if let Ok(ipv4) = host.parse::<Ipv4Addr>() {
+                    Host::Ipv4(ipv4)
+                } else if let Ok(ipv6) = host.parse::<Ipv6Addr>() {
+                    Host::Ipv6(ipv6)
+                } else {
+                    Host::Domain(host.as_str())
+                }
+            }),
+            port: self.port,
+            path: self.path.as_deref(),
+            query: self.query.as_deref(),
+            fragment: self.fragment.as_deref(),
+        }
+    }
+}
+
+/// How specific a URL is, for ordering routing rules
+///
+/// Returned by `Url::specificity`. Comparison is field by field, in
+/// declaration order: more path segments is more specific, then a
+/// longer (percent encoded) path, then more query pairs, then having
+/// a fragment. URLs which cannot be a base (`mailto:` etc.) are
+/// always the minimum, every field zero.
+///
+/// URLs which tie on every field compare equal, sort with a stable
+/// sort (`sort_by_key`) so such rules keep their relative order.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Specificity {
+    /// non-empty path segments
+    pub path_segments: usize,
+    /// length in bytes of the encoded path
+    pub path_len: usize,
+    /// query key value pairs
+    pub query_pairs: usize,
+    pub has_fragment: bool,
+}
+
 /// Origin defines a slightly incorrect origin structure
 #[derive(Clone, Debug)]
 pub struct Origin<'a> {
@@ -444,21 +1059,157 @@ impl<'a> Origin<'a> {
 
     /// `is_domain` checks if this is a domain
     pub fn is_domain(&self) -> bool {
-        match self.host {
-            Host::Domain(_) => true,
-            _ => false,
-        }
+        matches!(self.host, Host::Domain(_))
     }
 
     /// `get_domain()` returns the domain if this is a domain
     pub fn get_domain<'b>(&'b self) -> Option<&'b str> {
         match self.host {
-            Host::Domain(ref domain) => Some(domain),
+            Host::Domain(domain) => Some(domain),
             _ => None,
         }
     }
 }
 
+/// Headers relevant to a cross origin request
+///
+/// This is not CORS, it only removes some boilerplate which
+/// shows up in CORS middleware.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CorsHeaders {
+    /// serialized origin of the requesting URL, the value of the
+    /// `Origin` header.
+    pub origin: String,
+    /// value for `Access-Control-Allow-Origin`
+    pub access_control_allow_origin_pattern: String,
+}
+
+/// A version hint found in the path of a URL
+///
+/// See `Url::get_http_version_hint`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum HttpVersion {
+    /// `v1` or `v1.0`
+    V1_0,
+    /// `v1.1`
+    V1_1,
+    /// `v2` or `v2.0`
+    H2,
+    /// `v3` or `v3.0`
+    H3,
+    /// any other version segment, lowercased, such as `v4` or `v2.5`
+    Unknown(String),
+}
+impl HttpVersion {
+    /// `from_segment` recognizes `v` followed by a number, with an
+    /// optional minor version. Case is ignored.
+    pub fn from_segment(segment: &str) -> Option<HttpVersion> {
+        let segment = segment.to_ascii_lowercase();
+        let is_number = |arg: &str| !arg.is_empty() && arg.bytes().all(|b| b.is_ascii_digit());
+        let valid = match segment.strip_prefix('v') {
+            Option::Some(version) => {
+                let mut parts = version.splitn(2, '.');
+                is_number(parts.next().unwrap_or("")) && parts.next().map(is_number).unwrap_or(true)
+            }
+            Option::None => false,
+        };
+        if !valid {
+            return None;
+        }
+        Some(match segment.as_str() {
+            "v1" | "v1.0" => HttpVersion::V1_0,
+            "v1.1" => HttpVersion::V1_1,
+            "v2" | "v2.0" => HttpVersion::H2,
+            "v3" | "v3.0" => HttpVersion::H3,
+            _ => HttpVersion::Unknown(segment),
+        })
+    }
+}
+
+impl fmt::Display for HttpVersion {
+    /// displays the version as it appears in an HTTP request line,
+    /// `HTTP/1.1`, `HTTP/2`, etc.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            HttpVersion::V1_0 => f.write_str("HTTP/1.0"),
+            HttpVersion::V1_1 => f.write_str("HTTP/1.1"),
+            HttpVersion::H2 => f.write_str("HTTP/2"),
+            HttpVersion::H3 => f.write_str("HTTP/3"),
+            HttpVersion::Unknown(ref version) => write!(f, "HTTP/{}", version.trim_start_matches(['v', 'V'])),
+        }
+    }
+}
+
+/// Borrowed `Display` of a `Url`, see `Url::as_display`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct UrlDisplay<'a> {
+    string: &'a str,
+}
+impl<'a> Display for UrlDisplay<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.string)
+    }
+}
+
+/// How a navigation from one URL to another relates the two, see
+/// `Url::navigation_relation`
+///
+/// Variants are checked in declaration order, the first which fits
+/// is the answer. So when both the query and the fragment change it
+/// is `QueryOnly`, as the browser loads a new document.
+///
+/// There is no "same site" variant, as that needs the public suffix
+/// list, which this crate does not ship. Such navigations are
+/// `CrossOrigin`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum NavigationRelation {
+    /// the normalized strings are equal
+    Identical,
+    /// only the fragment differs, the document is the same
+    FragmentOnly,
+    /// both URLs share a tuple origin, and everything but the query
+    /// (and maybe the fragment) is the same
+    QueryOnly,
+    /// both URLs share a tuple origin, but the path (or the
+    /// userinfo) differs
+    SameOriginDifferentPath,
+    /// the origins differ, or either is opaque
+    CrossOrigin,
+}
+
+/// How a trailing `/` on a path is treated when preparing it
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TrailingSlash {
+    /// leave the path as it is
+    Preserve,
+    /// ensure the path ends with `/`
+    Add,
+    /// ensure the path does not end with `/`, unless it is the root
+    Remove,
+}
+
+/// `hash_bases` walks `string` once, returning its FNV-1a hash and a
+/// second, odd, hash. Changing either changes `Url::fingerprint` and
+/// `Url::hash_seeds`, which are documented as stable.
+fn hash_bases(string: &str) -> (u64, u64) {
+    const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+    const FX_SEED: u64 = 0x517c_c1b7_2722_0a95;
+
+    let (fnv, fx) = string.bytes().fold((FNV_OFFSET, 0u64), |(fnv, fx), byte| {
+        let fnv = (fnv ^ u64::from(byte)).wrapping_mul(FNV_PRIME);
+        let fx = (fx.rotate_left(5) ^ u64::from(byte)).wrapping_mul(FX_SEED);
+        (fnv, fx)
+    });
+    // splitmix64 finalizer, so the low bits of `fx` depend on every byte
+    let mut mixed = fx ^ (string.len() as u64);
+    mixed = (mixed ^ (mixed >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    mixed = (mixed ^ (mixed >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    mixed ^= mixed >> 31;
+    // odd, so `h1 + i * h2` never repeats for `i < 2^64`
+    (fnv, mixed | 1)
+}
+
 #[inline(always)]
 fn boilerplate<'a, T>(input: T, err: UrlFault) -> Option<Result<Box<str>, UrlFault>>
 where
@@ -488,7 +1239,7 @@ where
     T: Into<Option<&'a Box<str>>> + 'a,
 {
     match arg.into() {
-        Option::Some(data) => Some(&*data),
+        Option::Some(data) => Some(data.as_ref()),
         Option::None => None,
     }
 }
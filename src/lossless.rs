@@ -0,0 +1,61 @@
+//! Serde with-module which round trips the input byte for byte
+//!
+//! `serialize` writes `get_input()`, which is the normalized string
+//! whenever normalization changed nothing, so documents are written
+//! back exactly as they were read. A label recorded with
+//! `with_recorded_input` is only written when it parses to the same
+//! `Url`, anything else (an unredacted original, or text which is not
+//! a URL at all) falls back to `get_string()`.
+//!
+//! Deserializing is unchanged, the input is recorded as usual and the
+//! `Url` still compares, hashes, and displays by its normalized form.
+//!
+//! ```
+//! #[macro_use]
+//! extern crate serde_derive;
+//! extern crate serde_json;
+//! extern crate serde_url;
+//!
+//! use serde_url::Url;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Link {
+//!     #[serde(with = "serde_url::lossless")]
+//!     href: Url,
+//! }
+//!
+//! # fn main() {
+//! let doc = r#"{"href":"HTTPS://Google.com:443/a/./b"}"#;
+//! let link: Link = serde_json::from_str(doc).unwrap();
+//! assert_eq!(link.href, "https://google.com/a/b");
+//! assert_eq!(serde_json::to_string(&link).unwrap(), doc);
+//! # }
+//! ```
+
+use super::serde;
+use super::serde::Deserialize;
+use super::Url;
+
+/// `serialize` writes the input the `Url` was parsed from, see the
+/// module documentation for recorded labels
+pub fn serialize<S>(url: &Url, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    let input = url.get_input();
+    let parses_to_url = input == url.get_parsed_input() ||
+        Url::new(&input).map(|parsed| parsed == *url).unwrap_or(false);
+    if parses_to_url {
+        serializer.serialize_str(input)
+    } else {
+        serializer.serialize_str(url.get_string())
+    }
+}
+
+/// `deserialize` parses the URL, recording its input
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Url, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Url::deserialize(deserializer)
+}
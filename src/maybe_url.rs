@@ -0,0 +1,135 @@
+use std::fmt;
+
+use super::serde;
+use super::errors::UrlFault;
+use super::validator;
+use super::Url;
+
+/// A `Url` field which keeps invalid input instead of failing
+///
+/// Deserializing a `MaybeUrl` never fails because the URL is
+/// invalid, the original string and the fault are kept instead.
+/// Serializing writes the normalized string of a valid URL, and
+/// the original input, unchanged, of an invalid one.
+///
+/// When deserializing, a URL rejected by the installed
+/// `DeserializeValidator` is kept as `Invalid`, with the fault
+/// `UrlFault::ValidatorRejected`, just as `UrlOrString` keeps it as
+/// `Other`. `new` does not consult the validator.
+///
+/// ```
+/// extern crate serde_json;
+/// extern crate serde_url;
+///
+/// use serde_url::MaybeUrl;
+///
+/// # fn main() {
+/// let urls: Vec<MaybeUrl> = serde_json::from_str(r#"["https://google.com", "http://[::1"]"#).unwrap();
+/// assert!(urls[0].is_valid());
+/// assert!(!urls[1].is_valid());
+/// assert_eq!(serde_json::to_string(&urls).unwrap(), r#"["https://google.com/","http://[::1"]"#);
+/// # }
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum MaybeUrl {
+    Valid(Url),
+    Invalid { input: String, fault: UrlFault },
+}
+impl MaybeUrl {
+    /// `new` parses the input, keeping it if it is not a valid URL
+    pub fn new<S>(input: &S) -> MaybeUrl
+    where
+        S: AsRef<str>,
+    {
+        let input = input.as_ref();
+        match Url::new(&input) {
+            Ok(url) => MaybeUrl::Valid(url),
+            Err(fault) => MaybeUrl::Invalid {
+                input: input.to_string(),
+                fault,
+            },
+        }
+    }
+
+    /// `from_input` is `new`, plus the `DeserializeValidator`
+    fn from_input(input: &str) -> MaybeUrl {
+        match MaybeUrl::new(&input) {
+            MaybeUrl::Valid(ref url) if validator::validate(url).is_err() => MaybeUrl::Invalid {
+                input: input.to_string(),
+                fault: UrlFault::ValidatorRejected,
+            },
+            maybe_url => maybe_url,
+        }
+    }
+
+    /// `as_url` returns the parsed `Url`, if it was valid
+    pub fn as_url<'a>(&'a self) -> Option<&'a Url> {
+        match *self {
+            MaybeUrl::Valid(ref url) => Some(url),
+            MaybeUrl::Invalid { .. } => None,
+        }
+    }
+
+    /// `is_valid` checks if the input parsed
+    pub fn is_valid(&self) -> bool {
+        self.as_url().is_some()
+    }
+
+    /// `into_result` returns the `Url`, or the fault which stopped
+    /// it from parsing.
+    pub fn into_result(self) -> Result<Url, UrlFault> {
+        match self {
+            MaybeUrl::Valid(url) => Ok(url),
+            MaybeUrl::Invalid { fault, .. } => Err(fault),
+        }
+    }
+
+    /// `as_str` returns the normalized string of a valid URL, or
+    /// the original input of an invalid one.
+    pub fn as_str<'a>(&'a self) -> &'a str {
+        match *self {
+            MaybeUrl::Valid(ref url) => url.get_string(),
+            MaybeUrl::Invalid { ref input, .. } => input.as_str(),
+        }
+    }
+}
+impl From<Url> for MaybeUrl {
+    fn from(url: Url) -> MaybeUrl {
+        MaybeUrl::Valid(url)
+    }
+}
+impl fmt::Display for MaybeUrl {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+impl serde::Serialize for MaybeUrl {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+struct MaybeUrlVisitor;
+impl<'de> serde::de::Visitor<'de> for MaybeUrlVisitor {
+    type Value = MaybeUrl;
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a string")
+    }
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(MaybeUrl::from_input(value))
+    }
+}
+impl<'de> serde::Deserialize<'de> for MaybeUrl {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_str(MaybeUrlVisitor)
+    }
+}
@@ -0,0 +1,103 @@
+
+//! Serde with-module for `Vec<Url>` stored as a newline delimited string
+//!
+//! Each line is trimmed, blank lines and lines starting with `#` are
+//! skipped. Deserialization fails on the first invalid line, the
+//! error names its (1-based) line number and text. The `lenient`
+//! sub-module drops invalid lines instead. Serialization joins the
+//! URLs with `\n`.
+//!
+//! ```
+//! #[macro_use]
+//! extern crate serde_derive;
+//! extern crate serde_json;
+//! extern crate serde_url;
+//!
+//! use serde_url::Url;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Feed {
+//!     #[serde(with = "serde_url::newline_list")]
+//!     urls: Vec<Url>,
+//! }
+//!
+//! # fn main() {
+//! let data = r#"{"urls":"https://a.com/\n# comment\n\nhttps://b.com/\n"}"#;
+//! let feed: Feed = serde_json::from_str(data).unwrap();
+//! assert_eq!(feed.urls, vec![Url::new(&"https://a.com/").unwrap(), Url::new(&"https://b.com/").unwrap()]);
+//! # }
+//! ```
+
+use super::serde;
+use super::serde::Deserialize;
+use super::errors::UrlFault;
+use super::Url;
+
+/// `serialize` joins the URLs with `\n`
+pub fn serialize<S>(urls: &[Url], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_str(&join(urls))
+}
+
+/// `deserialize` parses every line, failing on the first invalid one
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<Url>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let data = String::deserialize(deserializer)?;
+    lines(&data)
+        .map(|(number, line, url)| {
+            url.map_err(|e| {
+                serde::de::Error::custom(format!(
+                    "line {}: {:?} is not a valid URL, {}",
+                    number,
+                    line,
+                    e
+                ))
+            })
+        })
+        .collect()
+}
+
+/// Newline delimited URL lists which drop invalid lines
+pub mod lenient {
+    use super::super::serde;
+    use super::super::serde::Deserialize;
+    use super::super::Url;
+
+    /// `serialize` joins the URLs with `\n`
+    pub fn serialize<S>(urls: &[Url], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        super::serialize(urls, serializer)
+    }
+
+    /// `deserialize` parses every line, dropping invalid ones
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<Url>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let data = String::deserialize(deserializer)?;
+        Ok(super::lines(&data).filter_map(|(_, _, url)| url.ok()).collect())
+    }
+}
+
+/// `lines` yields the 1-based line number, text, and parse result
+/// of every line which is not blank or a comment.
+fn lines<'a>(data: &'a str) -> impl Iterator<Item = (usize, &'a str, Result<Url, UrlFault>)> + 'a {
+    data.lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(number, line)| (number, line, Url::new(&line)))
+}
+
+fn join(urls: &[Url]) -> String {
+    urls.iter()
+        .map(|url| url.get_string())
+        .collect::<Vec<&str>>()
+        .join("\n")
+}
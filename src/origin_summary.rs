@@ -0,0 +1,191 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::iter::FromIterator;
+
+use super::serde;
+use super::url;
+use super::Url;
+
+/// The origin a URL is counted under, see `OriginSummary`
+///
+/// Tuple origins always carry their effective port, so
+/// `https://google.com` and `https://google.com:443` share a key.
+/// Opaque origins (`mailto:`, `data:`, `file:`, etc.) are bucketed
+/// by scheme alone, as they never compare equal to one another.
+///
+/// This displays (and serializes) as `scheme://host:port` for tuple
+/// origins, and `scheme:` for opaque ones.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum OriginKey {
+    Tuple {
+        scheme: String,
+        host: String,
+        port: u16,
+    },
+    Opaque {
+        scheme: String,
+    },
+}
+impl OriginKey {
+    /// `of` returns the key a `Url` is counted under
+    ///
+    /// ```
+    /// use serde_url::{Url, OriginKey};
+    ///
+    /// let key = OriginKey::of(&Url::new(&"https://google.com/a").unwrap());
+    /// assert_eq!(key.to_string(), "https://google.com:443");
+    ///
+    /// let key = OriginKey::of(&Url::new(&"mailto:a@google.com").unwrap());
+    /// assert_eq!(key.to_string(), "mailto:");
+    /// ```
+    pub fn of(url: &Url) -> OriginKey {
+        match url.data.get_url_data().origin() {
+            url::Origin::Tuple(scheme, host, port) => {
+                OriginKey::Tuple {
+                    scheme,
+                    host: host.to_string(),
+                    port,
+                }
+            }
+            url::Origin::Opaque(_) => OriginKey::Opaque { scheme: url.get_scheme().to_string() },
+        }
+    }
+
+    /// `is_opaque` checks if this is the bucket of an opaque origin
+    pub fn is_opaque(&self) -> bool {
+        match *self {
+            OriginKey::Opaque { .. } => true,
+            OriginKey::Tuple { .. } => false,
+        }
+    }
+}
+impl fmt::Display for OriginKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            OriginKey::Tuple { ref scheme, ref host, port } => write!(f, "{}://{}:{}", scheme, host, port),
+            OriginKey::Opaque { ref scheme } => write!(f, "{}:", scheme),
+        }
+    }
+}
+impl serde::Serialize for OriginKey {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+/// Counts of `Url`s per origin, for reports
+///
+/// Summaries can be built on separate threads and combined with
+/// `merge`. Iteration (and serialization, as a map of origin to
+/// count) is in `OriginKey` order, so output is stable.
+///
+/// ```
+/// use serde_url::{Url, OriginSummary};
+///
+/// let summary: OriginSummary = vec!["https://google.com/a", "https://google.com:443/b", "http://google.com/"]
+///     .into_iter()
+///     .map(|s| Url::new(&s).unwrap())
+///     .collect();
+///
+/// assert_eq!(summary.total(), 3);
+/// let (top, count) = summary.top_n(1)[0];
+/// assert_eq!((top.to_string(), count), ("https://google.com:443".to_string(), 2));
+/// ```
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct OriginSummary {
+    counts: BTreeMap<OriginKey, u64>,
+    total: u64,
+}
+impl OriginSummary {
+    /// `new` creates an empty `OriginSummary`
+    pub fn new() -> OriginSummary {
+        OriginSummary::default()
+    }
+
+    /// `add` counts one `Url`
+    pub fn add(&mut self, url: &Url) {
+        *self.counts.entry(OriginKey::of(url)).or_insert(0) += 1;
+        self.total += 1;
+    }
+
+    /// `merge` adds the counts of `other` into this summary
+    pub fn merge(&mut self, other: OriginSummary) {
+        for (key, count) in other.counts {
+            *self.counts.entry(key).or_insert(0) += count;
+        }
+        self.total += other.total;
+    }
+
+    /// `total` is the number of `Url`s counted
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// `len` is the number of distinct origins
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// `is_empty` checks if nothing has been counted
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// `get` returns the count for an origin, `0` if it was never seen
+    pub fn get(&self, key: &OriginKey) -> u64 {
+        self.counts.get(key).cloned().unwrap_or(0)
+    }
+
+    /// `iter` visits every origin and its count, in `OriginKey` order
+    pub fn iter<'a>(&'a self) -> impl Iterator<Item = (&'a OriginKey, u64)> + 'a {
+        self.counts.iter().map(|(key, count)| (key, *count))
+    }
+
+    /// `top_n` returns the `n` origins with the highest counts, ties
+    /// are broken by `OriginKey` order.
+    pub fn top_n<'a>(&'a self, n: usize) -> Vec<(&'a OriginKey, u64)> {
+        let mut top = self.iter().collect::<Vec<_>>();
+        top.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        top.truncate(n);
+        top
+    }
+}
+impl Extend<Url> for OriginSummary {
+    fn extend<I: IntoIterator<Item = Url>>(&mut self, iter: I) {
+        for url in iter {
+            self.add(&url);
+        }
+    }
+}
+impl<'a> Extend<&'a Url> for OriginSummary {
+    fn extend<I: IntoIterator<Item = &'a Url>>(&mut self, iter: I) {
+        for url in iter {
+            self.add(url);
+        }
+    }
+}
+impl FromIterator<Url> for OriginSummary {
+    fn from_iter<I: IntoIterator<Item = Url>>(iter: I) -> OriginSummary {
+        let mut summary = OriginSummary::new();
+        summary.extend(iter);
+        summary
+    }
+}
+impl<'a> FromIterator<&'a Url> for OriginSummary {
+    fn from_iter<I: IntoIterator<Item = &'a Url>>(iter: I) -> OriginSummary {
+        let mut summary = OriginSummary::new();
+        summary.extend(iter);
+        summary
+    }
+}
+impl serde::Serialize for OriginSummary {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_map(self.iter())
+    }
+}
@@ -0,0 +1,89 @@
+use super::errors::UrlFault;
+use super::Url;
+
+/// Restrictions applied to a URL after it parses
+///
+/// Everything is allowed by default, so `ParseOptions::new().parse`
+/// behaves exactly like `Url::new`.
+///
+/// ```
+/// use serde_url::{ParseOptions, UrlFault};
+///
+/// let mut options = ParseOptions::new();
+/// options.forbid_fragment(true).forbid_credentials(true);
+/// assert!(options.parse(&"https://hooks.google.com/notify").is_ok());
+/// assert_eq!(options.parse(&"https://hooks.google.com/notify#x"), Err(UrlFault::FragmentForbidden));
+/// assert_eq!(options.parse(&"https://jane@hooks.google.com/"), Err(UrlFault::CredentialsForbidden));
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct ParseOptions {
+    forbid_fragment: bool,
+    forbid_credentials: bool,
+    forbid_query: bool,
+}
+impl ParseOptions {
+    /// `new` allows everything
+    pub fn new() -> ParseOptions {
+        ParseOptions::default()
+    }
+
+    /// `forbid_fragment` rejects URLs with a fragment, even an
+    /// empty one, as `FragmentForbidden`.
+    pub fn forbid_fragment(&mut self, forbid: bool) -> &mut ParseOptions {
+        self.forbid_fragment = forbid;
+        self
+    }
+
+    /// `forbid_credentials` rejects URLs with a username or password
+    /// as `CredentialsForbidden`.
+    pub fn forbid_credentials(&mut self, forbid: bool) -> &mut ParseOptions {
+        self.forbid_credentials = forbid;
+        self
+    }
+
+    /// `forbid_query` rejects URLs with a query, even an empty one,
+    /// as `QueryForbidden`.
+    pub fn forbid_query(&mut self, forbid: bool) -> &mut ParseOptions {
+        self.forbid_query = forbid;
+        self
+    }
+
+    /// `parse` parses the input, then applies the restrictions
+    pub fn parse<S>(&self, input: &S) -> Result<Url, UrlFault>
+    where
+        S: AsRef<str>,
+    {
+        let url = Url::new(input)?;
+        self.check(&url)?;
+        Ok(url)
+    }
+
+    /// `check` applies the restrictions to an already parsed `Url`
+    pub fn check(&self, url: &Url) -> Result<(), UrlFault> {
+        let url_data = url.data.get_url_data();
+        if self.forbid_credentials && (!url_data.username().is_empty() || url_data.password().is_some()) {
+            return Err(UrlFault::CredentialsForbidden);
+        }
+        if self.forbid_query && url_data.query().is_some() {
+            return Err(UrlFault::QueryForbidden);
+        }
+        if self.forbid_fragment && url_data.fragment().is_some() {
+            return Err(UrlFault::FragmentForbidden);
+        }
+        Ok(())
+    }
+}
+
+/// `describe_violation` names the component of `url` which caused a
+/// `ParseOptions` fault, for error messages. Passwords are never
+/// included.
+pub fn describe_violation(fault: UrlFault, url: &Url) -> Option<String> {
+    let url_data = url.data.get_url_data();
+    match fault {
+        UrlFault::FragmentForbidden => url_data.fragment().map(|fragment| format!("fragment `#{}`", fragment)),
+        UrlFault::QueryForbidden => url_data.query().map(|query| format!("query `?{}`", query)),
+        UrlFault::CredentialsForbidden if url_data.username().is_empty() => Some("a password".to_string()),
+        UrlFault::CredentialsForbidden => Some(format!("credentials for user `{}`", url_data.username())),
+        _ => None,
+    }
+}
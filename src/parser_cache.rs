@@ -0,0 +1,197 @@
+//! Bounded cache of parsed URLs, keyed by their input
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
+use std::ptr;
+use std::sync::atomic::{AtomicPtr, Ordering};
+use std::sync::{Arc, Mutex};
+
+use super::errors::UrlFault;
+use super::internal::PrivateUrl;
+use super::Url;
+
+const MAX_SHARDS: usize = 16;
+
+/// The installed cache. Caches are leaked once installed, so parsing
+/// never takes a global lock, or counts references, to reach one.
+static GLOBAL: AtomicPtr<ParserCache> = AtomicPtr::new(ptr::null_mut());
+
+/// A bounded, least recently used, cache of parsed URLs
+///
+/// Parsing the same input twice returns the same shared `Url`, so
+/// hot inputs are only parsed once. Failed parses are not cached.
+/// Inputs longer than `max_key_len` bytes bypass the cache.
+///
+/// The cache is split into up to 16 shards, each with its own lock
+/// and its own share of `capacity`, so threads only contend when
+/// their inputs land in the same shard. Eviction is least recently
+/// used _within_ a shard, which approximates it over the whole cache.
+///
+/// ```
+/// use serde_url::ParserCache;
+///
+/// let cache = ParserCache::new(1024, 256);
+/// let a = cache.parse("https://google.com/").unwrap();
+/// let b = cache.parse("https://google.com/").unwrap();
+/// assert!(a.ptr_eq(&b));
+/// assert_eq!(cache.len(), 1);
+/// ```
+pub struct ParserCache {
+    shards: Box<[Mutex<Shard>]>,
+    max_key_len: usize,
+}
+impl ParserCache {
+    /// `new` creates a cache holding at most `capacity` URLs, whose
+    /// inputs are at most `max_key_len` bytes. A `capacity` of `0`
+    /// caches nothing.
+    pub fn new(capacity: usize, max_key_len: usize) -> ParserCache {
+        let count = capacity.clamp(1, MAX_SHARDS);
+        let shards = (0..count)
+            .map(|i| {
+                let share = capacity / count + if i < capacity % count { 1 } else { 0 };
+                Mutex::new(Shard::new(share))
+            })
+            .collect::<Vec<_>>()
+            .into_boxed_slice();
+        ParserCache { shards, max_key_len }
+    }
+
+    /// `parse` returns the cached `Url` for `input`, parsing (and
+    /// caching) it on a miss.
+    pub fn parse(&self, input: &str) -> Result<Url, UrlFault> {
+        if input.len() > self.max_key_len {
+            return Url::new(&input);
+        }
+        let shard = self.shard(input);
+        if let Option::Some(data) = lock(shard).get(input) {
+            return Ok(Url { data });
+        }
+        // parse without holding the lock, another thread may race us
+        let data = Arc::new(PrivateUrl::new(input)?);
+        let data = lock(shard).insert(input, data);
+        Ok(Url { data })
+    }
+
+    /// `len` is the number of cached URLs
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(|shard| lock(shard).entries.len()).sum()
+    }
+
+    /// `is_empty` checks if nothing is cached
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// `clear` removes every cached URL
+    pub fn clear(&self) {
+        for shard in self.shards.iter() {
+            let mut shard = lock(shard);
+            shard.entries.clear();
+            shard.order.clear();
+        }
+    }
+
+    fn shard<'a>(&'a self, input: &str) -> &'a Mutex<Shard> {
+        let mut hasher = DefaultHasher::new();
+        input.hash(&mut hasher);
+        &self.shards[(hasher.finish() % self.shards.len() as u64) as usize]
+    }
+}
+
+/// `set_global_parser_cache` installs the cache `Url::new_cached`,
+/// and the `cached` serde module, use. It replaces any previous one.
+///
+/// # Global State
+///
+/// As with `set_deserialize_validator` this is shared by the whole
+/// process, it should be set once during startup. Other threads may
+/// still be using a replaced cache, so it is emptied, but the (small)
+/// cache itself is never freed.
+pub fn set_global_parser_cache(cache: ParserCache) {
+    let cache = Box::into_raw(Box::new(cache));
+    retire(GLOBAL.swap(cache, Ordering::AcqRel));
+}
+
+/// `clear_global_parser_cache` removes the installed cache,
+/// `Url::new_cached` goes back to plain parsing.
+pub fn clear_global_parser_cache() {
+    retire(GLOBAL.swap(ptr::null_mut(), Ordering::AcqRel));
+}
+
+/// `parse_global` parses through the installed cache, if any. Either
+/// way finding it is a single atomic load.
+pub fn parse_global(input: &str) -> Result<Url, UrlFault> {
+    // SAFETY: installed caches are never freed, see `retire`
+    match unsafe { GLOBAL.load(Ordering::Acquire).as_ref() } {
+        Option::Some(cache) => cache.parse(input),
+        Option::None => Url::new(&input),
+    }
+}
+
+/// `retire` empties a cache which is no longer installed. It is not
+/// freed, as `parse_global` may still be using it.
+fn retire(cache: *mut ParserCache) {
+    // SAFETY: non-null pointers in `GLOBAL` come from `Box::into_raw`
+    // and are never freed
+    if let Option::Some(cache) = unsafe { cache.as_ref() } {
+        cache.clear();
+    }
+}
+
+fn lock<'a>(shard: &'a Mutex<Shard>) -> ::std::sync::MutexGuard<'a, Shard> {
+    shard.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// One shard, `order` maps the last use of each entry back to it
+struct Shard {
+    capacity: usize,
+    tick: u64,
+    entries: HashMap<Box<str>, (Arc<PrivateUrl>, u64)>,
+    order: BTreeMap<u64, Box<str>>,
+}
+impl Shard {
+    fn new(capacity: usize) -> Shard {
+        Shard {
+            capacity,
+            tick: 0,
+            entries: HashMap::new(),
+            order: BTreeMap::new(),
+        }
+    }
+
+    fn get(&mut self, input: &str) -> Option<Arc<PrivateUrl>> {
+        self.tick += 1;
+        let tick = self.tick;
+        let entry = self.entries.get_mut(input)?;
+        let last_used = entry.1;
+        entry.1 = tick;
+        let data = entry.0.clone();
+        if let Option::Some(key) = self.order.remove(&last_used) {
+            self.order.insert(tick, key);
+        }
+        Some(data)
+    }
+
+    fn insert(&mut self, input: &str, data: Arc<PrivateUrl>) -> Arc<PrivateUrl> {
+        if let Option::Some(existing) = self.get(input) {
+            return existing;
+        }
+        if self.capacity == 0 {
+            return data;
+        }
+        while self.entries.len() >= self.capacity {
+            let oldest = match self.order.keys().next() {
+                Option::Some(&tick) => tick,
+                Option::None => break,
+            };
+            if let Option::Some(key) = self.order.remove(&oldest) {
+                self.entries.remove(&key);
+            }
+        }
+        let key: Box<str> = input.into();
+        self.entries.insert(key.clone(), (data.clone(), self.tick));
+        self.order.insert(self.tick, key);
+        data
+    }
+}
@@ -0,0 +1,124 @@
+
+use std::fmt;
+use std::iter::FromIterator;
+
+use super::errors::UrlFault;
+use super::Url;
+
+/// A single, unencoded, path segment
+///
+/// Segments are percent encoded when they are added to a URL, so
+/// a `/` within a segment is kept as `%2F` rather than splitting it.
+/// Segments which are `.` or `..` are rejected as the parser would
+/// treat them as dot segments.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct PathSegment(String);
+impl PathSegment {
+    /// `new` validates a path segment
+    ///
+    /// ```
+    /// use serde_url::{PathSegment, UrlFault};
+    ///
+    /// assert!(PathSegment::new("users").is_ok());
+    /// assert_eq!(PathSegment::new(".."), Err(UrlFault::InvalidPathSegment));
+    /// ```
+    pub fn new<S>(segment: S) -> Result<PathSegment, UrlFault>
+    where
+        S: Into<String>,
+    {
+        let segment = segment.into();
+        match segment.as_str() {
+            "." | ".." => Err(UrlFault::InvalidPathSegment),
+            _ => Ok(PathSegment(segment)),
+        }
+    }
+
+    /// `as_str` returns the unencoded segment
+    pub fn as_str<'a>(&'a self) -> &'a str {
+        self.0.as_str()
+    }
+}
+impl AsRef<str> for PathSegment {
+    fn as_ref<'a>(&'a self) -> &'a str {
+        self.as_str()
+    }
+}
+impl fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Collects `PathSegment`s and appends them to a base `Url`
+///
+/// ```
+/// use serde_url::{Url, PathSegment, PathBuilder};
+///
+/// let base = Url::new(&"https://api.google.com/v1/").unwrap();
+/// let user_id = "jane doe";
+/// let path = vec!["users", user_id, "a/b"]
+///     .into_iter()
+///     .map(|seg| PathSegment::new(seg).unwrap())
+///     .collect::<PathBuilder>();
+/// let url = path.with_base(base);
+/// assert_eq!(url, "https://api.google.com/v1/users/jane%20doe/a%2Fb");
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct PathBuilder {
+    segments: Vec<PathSegment>,
+}
+impl PathBuilder {
+    /// `new` creates an empty `PathBuilder`
+    pub fn new() -> PathBuilder {
+        PathBuilder::default()
+    }
+
+    /// `push` appends another segment
+    pub fn push(&mut self, segment: PathSegment) -> &mut PathBuilder {
+        self.segments.push(segment);
+        self
+    }
+
+    /// `get_segments` returns the segments collected so far
+    pub fn get_segments<'a>(&'a self) -> &'a [PathSegment] {
+        &self.segments
+    }
+
+    /// `with_base` appends the segments to the path of `base`. A
+    /// trailing `/` on the base path is not doubled. The query and
+    /// fragment of `base` are kept.
+    ///
+    /// URLs which cannot be a base (`mailto:` etc.) have no path
+    /// segments, so they are returned unchanged.
+    pub fn with_base(&self, base: Url) -> Url {
+        if self.segments.is_empty() {
+            return base;
+        }
+        let mut url_data = base.data.get_url_data().clone();
+        {
+            let mut segments = match url_data.path_segments_mut() {
+                Ok(segments) => segments,
+                Err(_) => return base,
+            };
+            segments.pop_if_empty();
+            segments.extend(self.segments.iter().map(PathSegment::as_str));
+        }
+        Url::from_url_data(url_data).unwrap_or(base)
+    }
+}
+impl FromIterator<PathSegment> for PathBuilder {
+    fn from_iter<I>(iter: I) -> PathBuilder
+    where
+        I: IntoIterator<Item = PathSegment>,
+    {
+        PathBuilder { segments: iter.into_iter().collect() }
+    }
+}
+impl Extend<PathSegment> for PathBuilder {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = PathSegment>,
+    {
+        self.segments.extend(iter)
+    }
+}
@@ -0,0 +1,54 @@
+use std::borrow::Cow;
+
+use super::errors::UrlFault;
+use super::internal::Host;
+use super::Url;
+
+/// The origin `Url::map_origin` hands to its closure
+///
+/// Unlike `Origin` the port is optional: it is the explicit port,
+/// `None` when the URL does not state one. Handing it back unchanged
+/// keeps the URL's port as it was.
+#[derive(Clone, Debug)]
+pub struct RewriteOrigin<'a> {
+    pub scheme: &'a str,
+    pub host: Host<&'a str>,
+    pub port: Option<u16>,
+}
+
+/// `rewrite_all` applies `Url::map_origin` to every URL of a slice,
+/// replacing those `f` maps to a new host and port. URLs which are
+/// not mapped are left untouched. The indices of URLs which could
+/// not be rebuilt are returned with their fault, those URLs are
+/// left untouched as well.
+///
+/// ```
+/// use std::borrow::Cow;
+/// use serde_url::{Url, Host, rewrite_all};
+///
+/// let mut urls = vec![
+///     Url::new(&"https://old.google.com/a").unwrap(),
+///     Url::new(&"https://other.google.com/b").unwrap(),
+/// ];
+/// let failures = rewrite_all(&mut urls, |origin| match origin.host {
+///     Host::Domain("old.google.com") => Some((Cow::Borrowed("new.google.com"), None)),
+///     _ => None,
+/// });
+/// assert!(failures.is_empty());
+/// assert_eq!(urls[0], "https://new.google.com/a");
+/// assert_eq!(urls[1], "https://other.google.com/b");
+/// ```
+pub fn rewrite_all<F>(urls: &mut [Url], f: F) -> Vec<(usize, UrlFault)>
+where
+    F: for<'a> Fn(&RewriteOrigin<'a>) -> Option<(Cow<'a, str>, Option<u16>)>,
+{
+    let mut failures = Vec::new();
+    for (index, url) in urls.iter_mut().enumerate() {
+        match url.map_origin(&f) {
+            Ok(Option::Some(rewritten)) => *url = rewritten,
+            Ok(Option::None) => {}
+            Err(fault) => failures.push((index, fault)),
+        }
+    }
+    failures
+}
@@ -0,0 +1,154 @@
+//! A strict RFC 3986 `URI` syntax check
+//!
+//! The WHATWG parser leaves characters such as `|`, `^`, `{`, and
+//! stray `%` signs unencoded in places RFC 3986 forbids them. This
+//! walks the `URI` production of the RFC's ABNF by hand.
+
+use std::net::Ipv6Addr;
+
+/// `is_uri` checks `input` against the `URI` rule of RFC 3986
+///
+/// ```text
+/// URI       = scheme ":" hier-part [ "?" query ] [ "#" fragment ]
+/// hier-part = "//" authority path-abempty
+///           / path-absolute / path-rootless / path-empty
+/// ```
+pub fn is_uri(input: &str) -> bool {
+    let (rest, fragment) = split_once(input, '#');
+    let (rest, query) = split_once(rest, '?');
+    let (scheme, hier_part) = match rest.find(':') {
+        Option::Some(colon) => (&rest[..colon], &rest[colon + 1..]),
+        Option::None => return false,
+    };
+    is_scheme(scheme) &&
+        is_hier_part(hier_part) &&
+        query.map(is_query_or_fragment).unwrap_or(true) &&
+        fragment.map(is_query_or_fragment).unwrap_or(true)
+}
+
+fn split_once(input: &str, delimiter: char) -> (&str, Option<&str>) {
+    match input.find(delimiter) {
+        Option::Some(at) => (&input[..at], Some(&input[at + 1..])),
+        Option::None => (input, None),
+    }
+}
+
+/// `scheme = ALPHA *( ALPHA / DIGIT / "+" / "-" / "." )`
+fn is_scheme(scheme: &str) -> bool {
+    let mut bytes = scheme.bytes();
+    match bytes.next() {
+        Option::Some(first) if first.is_ascii_alphabetic() => {}
+        _ => return false,
+    }
+    bytes.all(|b| b.is_ascii_alphanumeric() || b == b'+' || b == b'-' || b == b'.')
+}
+
+fn is_hier_part(hier_part: &str) -> bool {
+    match hier_part.strip_prefix("//") {
+        Option::Some(rest) => {
+            let end = rest.find('/').unwrap_or(rest.len());
+            is_authority(&rest[..end]) && is_path(&rest[end..])
+        }
+        // `path-absolute` may not begin with `//`, that is handled above
+        Option::None => is_path(hier_part),
+    }
+}
+
+/// `authority = [ userinfo "@" ] host [ ":" port ]`
+fn is_authority(authority: &str) -> bool {
+    let (userinfo, host_port) = match authority.find('@') {
+        Option::Some(at) => (&authority[..at], &authority[at + 1..]),
+        Option::None => ("", authority),
+    };
+    if !is_encoded(userinfo, |b| is_unreserved(b) || is_sub_delim(b) || b == b':') {
+        return false;
+    }
+    let (host, port) = if host_port.starts_with('[') {
+        match host_port.find(']') {
+            Option::Some(close) => (&host_port[..close + 1], &host_port[close + 1..]),
+            Option::None => return false,
+        }
+    } else {
+        match host_port.find(':') {
+            Option::Some(colon) => (&host_port[..colon], &host_port[colon..]),
+            Option::None => (host_port, ""),
+        }
+    };
+    let port_ok = port.is_empty() || (port.starts_with(':') && port[1..].bytes().all(|b| b.is_ascii_digit()));
+    port_ok && is_host(host)
+}
+
+/// `host = IP-literal / IPv4address / reg-name`, where every
+/// `IPv4address` is also a `reg-name`
+fn is_host(host: &str) -> bool {
+    match host.strip_prefix('[').and_then(|host| host.strip_suffix(']')) {
+        Option::Some(literal) => is_ip_literal(literal),
+        Option::None => is_encoded(host, |b| is_unreserved(b) || is_sub_delim(b)),
+    }
+}
+
+/// `IP-literal = "[" ( IPv6address / IPvFuture ) "]"`, with
+/// `IPvFuture = "v" 1*HEXDIG "." 1*( unreserved / sub-delims / ":" )`
+fn is_ip_literal(literal: &str) -> bool {
+    if literal.starts_with('v') || literal.starts_with('V') {
+        let (version, address) = match literal[1..].find('.') {
+            Option::Some(dot) => (&literal[1..dot + 1], &literal[dot + 2..]),
+            Option::None => return false,
+        };
+        !version.is_empty() &&
+            version.bytes().all(|b| b.is_ascii_hexdigit()) &&
+            !address.is_empty() &&
+            address.bytes().all(|b| is_unreserved(b) || is_sub_delim(b) || b == b':')
+    } else {
+        literal.parse::<Ipv6Addr>().is_ok()
+    }
+}
+
+/// `path = *( "/" / pchar )`, which covers every path form once the
+/// authority has been handled.
+fn is_path(path: &str) -> bool {
+    is_encoded(path, |b| is_pchar(b) || b == b'/')
+}
+
+/// `query = fragment = *( pchar / "/" / "?" )`
+fn is_query_or_fragment(component: &str) -> bool {
+    is_encoded(component, |b| is_pchar(b) || b == b'/' || b == b'?')
+}
+
+/// `is_encoded` checks every byte is either `allowed`, or part of a
+/// `pct-encoded` triplet.
+fn is_encoded<F>(component: &str, allowed: F) -> bool
+where
+    F: Fn(u8) -> bool,
+{
+    let bytes = component.as_bytes();
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%' {
+            match bytes.get(index + 1..index + 3) {
+                Option::Some(hex) if hex.iter().all(u8::is_ascii_hexdigit) => index += 3,
+                _ => return false,
+            }
+        } else if allowed(bytes[index]) {
+            index += 1;
+        } else {
+            return false;
+        }
+    }
+    true
+}
+
+/// `pchar = unreserved / pct-encoded / sub-delims / ":" / "@"`
+fn is_pchar(b: u8) -> bool {
+    is_unreserved(b) || is_sub_delim(b) || b == b':' || b == b'@'
+}
+
+/// `unreserved = ALPHA / DIGIT / "-" / "." / "_" / "~"`
+fn is_unreserved(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'-' || b == b'.' || b == b'_' || b == b'~'
+}
+
+/// `sub-delims = "!" / "$" / "&" / "'" / "(" / ")" / "*" / "+" / "," / ";" / "="`
+fn is_sub_delim(b: u8) -> bool {
+    b"!$&'()*+,;=".contains(&b)
+}
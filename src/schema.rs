@@ -0,0 +1,114 @@
+use super::url;
+use super::errors::UrlFault;
+use super::scheme::IntoSchemeMatcher;
+use super::Url;
+
+/// Custom normalization rules for a protocol, see `Url::new_with_schema`
+///
+/// ```
+/// extern crate url;
+/// extern crate serde_url;
+///
+/// use serde_url::{Url, UrlFault, Schema};
+///
+/// /// `git+ssh` URLs, with the `.git` suffix removed
+/// struct GitSsh;
+/// impl Schema for GitSsh {
+///     fn normalize(&self, url: &mut url::Url) -> Result<(), UrlFault> {
+///         if url.scheme() != "git+ssh" {
+///             return Err(UrlFault::InvalidScheme);
+///         }
+///         let path = url.path().trim_end_matches(".git").to_string();
+///         url.set_path(&path);
+///         Ok(())
+///     }
+///     fn default_port(&self) -> Option<u16> {
+///         Some(22)
+///     }
+///     fn is_secure(&self) -> bool {
+///         true
+///     }
+/// }
+///
+/// # fn main() {
+/// let url = Url::new_with_schema("git+ssh://github.com:22/rust-lang/rust.git", &GitSsh).unwrap();
+/// assert_eq!(url, "git+ssh://github.com/rust-lang/rust");
+/// # }
+/// ```
+pub trait Schema {
+    /// `normalize` is called after the input is parsed, it may
+    /// modify the URL, or reject it.
+    fn normalize(&self, url: &mut url::Url) -> Result<(), UrlFault>;
+
+    /// `default_port` is removed from URLs which state it
+    fn default_port(&self) -> Option<u16>;
+
+    /// `is_secure` reports if the protocol is encrypted
+    fn is_secure(&self) -> bool;
+}
+
+/// `require_scheme` rejects URLs without the expected scheme
+fn require_scheme(url: &url::Url, scheme: &str) -> Result<(), UrlFault> {
+    if scheme.matches_scheme(url.scheme()) {
+        Ok(())
+    } else {
+        Err(UrlFault::InvalidScheme)
+    }
+}
+
+/// `Schema` accepting only `http` URLs
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct HttpSchema;
+impl Schema for HttpSchema {
+    fn normalize(&self, url: &mut url::Url) -> Result<(), UrlFault> {
+        require_scheme(url, "http")
+    }
+    fn default_port(&self) -> Option<u16> {
+        Some(80)
+    }
+    fn is_secure(&self) -> bool {
+        false
+    }
+}
+
+/// `Schema` accepting only `https` URLs
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct HttpsSchema;
+impl Schema for HttpsSchema {
+    fn normalize(&self, url: &mut url::Url) -> Result<(), UrlFault> {
+        require_scheme(url, "https")
+    }
+    fn default_port(&self) -> Option<u16> {
+        Some(443)
+    }
+    fn is_secure(&self) -> bool {
+        true
+    }
+}
+
+/// `Schema` accepting only `ftp` URLs
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct FtpSchema;
+impl Schema for FtpSchema {
+    fn normalize(&self, url: &mut url::Url) -> Result<(), UrlFault> {
+        require_scheme(url, "ftp")
+    }
+    fn default_port(&self) -> Option<u16> {
+        Some(21)
+    }
+    fn is_secure(&self) -> bool {
+        false
+    }
+}
+
+/// `parse` applies `schema` to `input`, see `Url::new_with_schema`
+pub fn parse<S: Schema + ?Sized>(input: &str, schema: &S) -> Result<Url, UrlFault> {
+    let mut url_data = url::Url::parse(input).map_err(|err| UrlFault::from_parse_error(err, input))?;
+    schema.normalize(&mut url_data)?;
+    if url_data.port().is_some() && url_data.port() == schema.default_port() {
+        url_data
+            .set_port(None)
+            .map_err(|()| UrlFault::from(url::ParseError::InvalidPort))?;
+    }
+    Url::from_url_data(url_data)
+}
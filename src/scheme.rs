@@ -0,0 +1,202 @@
+
+use std::fmt;
+
+/// Conventional default ports of schemes seen in URLs, sorted by
+/// scheme for `binary_search_by_key`. Not all of them are registered
+/// with IANA, some are only the port the software listens on out of
+/// the box. This is a superset of the parser's defaults.
+const WELL_KNOWN_PORTS: &[(&str, u16)] = &[
+    ("amqp", 5672),
+    ("amqps", 5671),
+    ("cassandra", 9042),
+    ("couchdb", 5984),
+    ("dns", 53),
+    ("ftp", 21),
+    ("ftps", 990),
+    ("git", 9418),
+    ("gopher", 70),
+    ("http", 80),
+    ("https", 443),
+    ("imap", 143),
+    ("imaps", 993),
+    ("ipp", 631),
+    ("irc", 6667),
+    ("ircs", 6697),
+    ("kafka", 9092),
+    ("ldap", 389),
+    ("ldaps", 636),
+    ("memcached", 11211),
+    ("mongodb", 27017),
+    ("mqtt", 1883),
+    ("mqtts", 8883),
+    ("mysql", 3306),
+    ("nats", 4222),
+    ("nfs", 2049),
+    ("nntp", 119),
+    ("ntp", 123),
+    ("pop3", 110),
+    ("pop3s", 995),
+    ("postgres", 5432),
+    ("postgresql", 5432),
+    ("rdp", 3389),
+    ("redis", 6379),
+    ("rsync", 873),
+    ("rtsp", 554),
+    ("sftp", 22),
+    ("sip", 5060),
+    ("sips", 5061),
+    ("smtp", 25),
+    ("smtps", 465),
+    ("snmp", 161),
+    ("ssh", 22),
+    ("stomp", 61613),
+    ("svn", 3690),
+    ("telnet", 23),
+    ("vnc", 5900),
+    ("ws", 80),
+    ("wss", 443),
+    ("xmpp", 5222),
+    ("zookeeper", 2181),
+];
+
+/// `scheme_to_default_port` returns the conventional default port of
+/// a scheme, from a table much larger than the one the parser uses
+/// to drop default ports. Schemes are matched ignoring case, and the
+/// parser's defaults take priority over the table.
+///
+/// This is the one lookup behind `Url::get_effective_port`,
+/// `Url::get_well_known_port`, `SchemeData`, and
+/// `Url::with_scheme_adjust_port`.
+///
+/// ```
+/// use serde_url::scheme_to_default_port;
+///
+/// assert_eq!(scheme_to_default_port("postgres"), Some(5432));
+/// assert_eq!(scheme_to_default_port("MQTT"), Some(1883));
+/// assert_eq!(scheme_to_default_port("https"), Some(443));
+/// assert_eq!(scheme_to_default_port("made-up"), None);
+/// ```
+pub fn scheme_to_default_port(scheme: &str) -> Option<u16> {
+    let scheme = scheme.to_ascii_lowercase();
+    default_port(&scheme).or_else(|| {
+        WELL_KNOWN_PORTS
+            .binary_search_by_key(&scheme.as_str(), |&(name, _)| name)
+            .ok()
+            .map(|index| WELL_KNOWN_PORTS[index].1)
+    })
+}
+
+/// Commonly used schemes
+///
+/// This exists so scheme comparisons can be written without
+/// string literals scattered everywhere.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Scheme {
+    Http,
+    Https,
+    Ws,
+    Wss,
+    Ftp,
+    File,
+}
+impl Scheme {
+    /// `as_str` returns the lowercase name of the scheme
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Scheme::Http => "http",
+            Scheme::Https => "https",
+            Scheme::Ws => "ws",
+            Scheme::Wss => "wss",
+            Scheme::Ftp => "ftp",
+            Scheme::File => "file",
+        }
+    }
+}
+impl Scheme {
+    /// `default_port` returns the port implied when a URL with
+    /// this scheme does not state one.
+    pub fn default_port(&self) -> Option<u16> {
+        default_port(self.as_str())
+    }
+}
+impl fmt::Display for Scheme {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// What the scheme of a URL means, see `Url::get_scheme_data`
+///
+/// `ws` and `wss` are not `Http`, they are reported as `Custom`.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum SchemeData {
+    /// `http` or `https`
+    Http { default_port: u16, use_tls: bool },
+    /// `ftp`
+    Ftp { default_port: u16 },
+    File,
+    Data,
+    Blob,
+    Mailto,
+    /// any other scheme, `name` is lowercase
+    Custom { name: String },
+}
+impl SchemeData {
+    /// `from_scheme` classifies a (lowercase) scheme, default ports
+    /// come from `scheme_to_default_port`.
+    pub fn from_scheme(scheme: &str) -> SchemeData {
+        match (scheme, scheme_to_default_port(scheme)) {
+            ("http", Option::Some(port)) => SchemeData::Http { default_port: port, use_tls: false },
+            ("https", Option::Some(port)) => SchemeData::Http { default_port: port, use_tls: true },
+            ("ftp", Option::Some(port)) => SchemeData::Ftp { default_port: port },
+            ("file", _) => SchemeData::File,
+            ("data", _) => SchemeData::Data,
+            ("blob", _) => SchemeData::Blob,
+            ("mailto", _) => SchemeData::Mailto,
+            (name, _) => SchemeData::Custom { name: name.to_string() },
+        }
+    }
+}
+
+/// Anything a scheme can be compared against.
+///
+/// Comparisons are ASCII case-insensitive, as the parser always
+/// lowercases schemes but user input often isn't.
+pub trait IntoSchemeMatcher {
+    /// `matches_scheme` checks the (lowercase) scheme of a URL
+    fn matches_scheme(&self, scheme: &str) -> bool;
+}
+impl IntoSchemeMatcher for Scheme {
+    fn matches_scheme(&self, scheme: &str) -> bool {
+        self.as_str().eq_ignore_ascii_case(scheme)
+    }
+}
+impl<'a> IntoSchemeMatcher for &'a str {
+    fn matches_scheme(&self, scheme: &str) -> bool {
+        self.eq_ignore_ascii_case(scheme)
+    }
+}
+impl IntoSchemeMatcher for String {
+    fn matches_scheme(&self, scheme: &str) -> bool {
+        self.eq_ignore_ascii_case(scheme)
+    }
+}
+impl<'a, T: IntoSchemeMatcher> IntoSchemeMatcher for &'a T {
+    fn matches_scheme(&self, scheme: &str) -> bool {
+        (*self).matches_scheme(scheme)
+    }
+}
+
+/// `default_port` returns the port the url parser considers the
+/// default for a (lowercase) scheme. Only what the parser drops
+/// should be decided by this, anything else uses
+/// `scheme_to_default_port`.
+pub fn default_port(scheme: &str) -> Option<u16> {
+    match scheme {
+        "http" | "ws" => Some(80),
+        "https" | "wss" => Some(443),
+        "ftp" => Some(21),
+        "gopher" => Some(70),
+        _ => None,
+    }
+}
@@ -0,0 +1,102 @@
+//! Serde with-module writing URLs scheme relative (`//host/path`)
+//!
+//! Pages served over both `http` and `https` link to assets without
+//! a scheme, so the browser uses the page's. `serialize` writes
+//! `Url::to_scheme_relative`, URLs without an authority (`mailto:`,
+//! `data:`, etc.) are an error. `deserialize` accepts absolute URLs
+//! as they are, and resolves scheme relative ones with `https`.
+//!
+//! Another default scheme is used by wrapping
+//! `deserialize_with_default_scheme`.
+//!
+//! ```
+//! #[macro_use]
+//! extern crate serde_derive;
+//! extern crate serde;
+//! extern crate serde_json;
+//! extern crate serde_url;
+//!
+//! use serde_url::Url;
+//!
+//! fn internal_asset<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Url, D::Error> {
+//!     serde_url::scheme_relative::deserialize_with_default_scheme(deserializer, "http")
+//! }
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Assets {
+//!     #[serde(with = "serde_url::scheme_relative")]
+//!     script: Url,
+//!     #[serde(serialize_with = "serde_url::scheme_relative::serialize", deserialize_with = "internal_asset")]
+//!     internal: Url,
+//! }
+//!
+//! # fn main() {
+//! let doc = r#"{"script":"//cdn.google.com/app.js","internal":"//build.local/app.js"}"#;
+//! let assets: Assets = serde_json::from_str(doc).unwrap();
+//! assert_eq!(assets.script, "https://cdn.google.com/app.js");
+//! assert_eq!(assets.internal, "http://build.local/app.js");
+//! assert_eq!(serde_json::to_string(&assets).unwrap(), doc);
+//! # }
+//! ```
+
+use std::fmt;
+
+use super::serde;
+use super::validator;
+use super::Url;
+
+/// `serialize` writes the URL without its scheme
+pub fn serialize<S>(url: &Url, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    match url.to_scheme_relative() {
+        Option::Some(relative) => serializer.serialize_str(&relative),
+        Option::None => Err(serde::ser::Error::custom(format!("{} cannot be written scheme relative", url))),
+    }
+}
+
+/// `deserialize` reads an absolute, or scheme relative, URL. The
+/// latter are given the `https` scheme.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Url, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    deserialize_with_default_scheme(deserializer, "https")
+}
+
+/// `deserialize_with_default_scheme` is `deserialize`, giving scheme
+/// relative URLs `scheme` instead.
+pub fn deserialize_with_default_scheme<'de, D>(deserializer: D, scheme: &str) -> Result<Url, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    deserializer.deserialize_str(SchemeRelativeVisitor { scheme })
+}
+
+struct SchemeRelativeVisitor<'a> {
+    scheme: &'a str,
+}
+impl<'a, 'de> serde::de::Visitor<'de> for SchemeRelativeVisitor<'a> {
+    type Value = Url;
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "an absolute, or scheme relative, Url")
+    }
+    fn visit_str<E>(self, value: &str) -> Result<Url, E>
+    where
+        E: serde::de::Error,
+    {
+        let url = if value.starts_with("//") {
+            Url::new(&format!("{}:{}", self.scheme, value))
+        } else {
+            Url::new(&value)
+        };
+        let url = url
+            .map_err(|e| format!("{:?}", e))
+            .map_err(serde::de::Error::custom)?;
+        validator::validate(&url)
+            .map_err(|msg| format!("{} ({})", msg, url))
+            .map_err(serde::de::Error::custom)?;
+        Ok(url)
+    }
+}
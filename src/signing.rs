@@ -0,0 +1,127 @@
+use std::error::Error;
+use std::fmt;
+use std::str;
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use super::Url;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Reasons a signed `Url` fails verification
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum SignatureError {
+    /// the `sig` or `expires` parameter is absent
+    Missing,
+    /// the `expires` timestamp has passed
+    Expired,
+    /// the signature does not match the URL
+    Mismatch,
+}
+impl fmt::Display for SignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let msg = match *self {
+            SignatureError::Missing => "URL is not signed, `sig` or `expires` is missing",
+            SignatureError::Expired => "signed URL has expired",
+            SignatureError::Mismatch => "signature does not match the URL",
+        };
+        write!(f, "{:?} {}", self, msg)
+    }
+}
+impl Error for SignatureError {}
+
+impl Url {
+    /// `sign` returns an expiring link, this URL with an `expires`
+    /// parameter (seconds since the unix epoch) and a `sig`
+    /// parameter holding the hex HMAC-SHA256 of the URL. Any
+    /// existing `expires` or `sig` parameters are replaced.
+    ///
+    /// The signature covers the scheme, authority, path, and every
+    /// other query parameter. Parameters are sorted first, so their
+    /// order does not matter. The fragment is not signed.
+    ///
+    /// ```
+    /// use serde_url::{Url, SignatureError};
+    ///
+    /// let url = Url::new(&"https://cdn.google.com/video.mp4?quality=hd").unwrap();
+    /// let signed = url.sign(b"secret", 1_700_000_000);
+    /// assert!(signed.get_string().contains("expires=1700000000&sig="));
+    /// assert_eq!(signed.verify_signature(b"secret", 1_600_000_000), Ok(()));
+    /// assert_eq!(signed.verify_signature(b"secret", 1_800_000_000), Err(SignatureError::Expired));
+    /// assert_eq!(signed.verify_signature(b"other", 1_600_000_000), Err(SignatureError::Mismatch));
+    /// ```
+    pub fn sign(&self, key: &[u8], expires_at_unix: u64) -> Url {
+        let url = self.strip_query_params(&["expires", "sig"])
+            .try_add_query(&format!("expires={}", expires_at_unix))
+            .unwrap_or_else(|_| self.clone());
+        let sig = to_hex(&mac(key, &url).finalize().into_bytes());
+        url.try_add_query(&format!("sig={}", sig))
+            .unwrap_or(url)
+    }
+
+    /// `verify_signature` checks a URL produced by `sign`, with the
+    /// same key, has not been modified or expired at `now_unix`.
+    /// The comparison is done in constant time.
+    pub fn verify_signature(&self, key: &[u8], now_unix: u64) -> Result<(), SignatureError> {
+        let find = |name: &str| {
+            self.raw_query_pairs()
+                .filter(|&(key, _)| key == name)
+                .map(|(_, value)| value.unwrap_or(""))
+                .next()
+        };
+        let (sig, expires) = match (find("sig"), find("expires")) {
+            (Option::Some(sig), Option::Some(expires)) => (sig, expires),
+            _ => return Err(SignatureError::Missing),
+        };
+        let sig = from_hex(sig).ok_or(SignatureError::Mismatch)?;
+        mac(key, self)
+            .verify_slice(&sig)
+            .map_err(|_| SignatureError::Mismatch)?;
+        let expires = expires.parse::<u64>().map_err(|_| SignatureError::Mismatch)?;
+        if now_unix > expires {
+            return Err(SignatureError::Expired);
+        }
+        Ok(())
+    }
+}
+
+/// `mac` computes the HMAC of the canonical form of `url`, with
+/// the query pairs sorted and the `sig` parameter removed.
+fn mac(key: &[u8], url: &Url) -> HmacSha256 {
+    let mut pairs = url.raw_query_pairs()
+        .filter(|&(key, _)| key != "sig")
+        .collect::<Vec<(&str, Option<&str>)>>();
+    pairs.sort();
+    let query = pairs
+        .iter()
+        .map(|&(key, value)| {
+            match value {
+                Option::Some(value) => format!("{}={}", key, value),
+                Option::None => key.to_string(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("&");
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(url.data.get_string_before_query().as_bytes());
+    mac.update(b"?");
+    mac.update(query.as_bytes());
+    mac
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            str::from_utf8(pair)
+                .ok()
+                .filter(|pair| pair.len() == 2)
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+        })
+        .collect()
+}
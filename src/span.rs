@@ -0,0 +1,163 @@
+
+use std::ops::Range;
+
+/// The components of a URL, used to locate them within the input
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Component {
+    Scheme,
+    Username,
+    Password,
+    Host,
+    Port,
+    Path,
+    Query,
+    Fragment,
+}
+
+/// `Spans` are the byte ranges of each component within an input
+#[derive(Clone, Default, Debug)]
+pub struct Spans {
+    scheme: Option<Range<usize>>,
+    username: Option<Range<usize>>,
+    password: Option<Range<usize>>,
+    host: Option<Range<usize>>,
+    port: Option<Range<usize>>,
+    path: Option<Range<usize>>,
+    query: Option<Range<usize>>,
+    fragment: Option<Range<usize>>,
+}
+impl Spans {
+    /// `scan` splits an input along the same boundaries the url
+    /// parser uses. Nothing is validated, this is only meaningful
+    /// for an input which has already been parsed successfully.
+    pub fn scan(input: &str) -> Spans {
+        let mut spans = Spans::default();
+        let bytes = input.as_bytes();
+
+        // the parser ignores leading and trailing C0 controls and spaces
+        let start = bytes.iter().position(|b| *b > b' ').unwrap_or(bytes.len());
+        let end = bytes.iter().rposition(|b| *b > b' ').map(|i| i + 1).unwrap_or(start);
+
+        let colon = match input[start..end].find(':') {
+            Option::Some(colon) => start + colon,
+            Option::None => return spans,
+        };
+        let scheme = &input[start..colon];
+        let valid_scheme = scheme.starts_with(|c: char| c.is_ascii_alphabetic()) &&
+            scheme.chars().all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.');
+        if !valid_scheme {
+            return spans;
+        }
+        spans.scheme = non_empty(start..colon);
+        let special = is_special(&scheme.to_ascii_lowercase());
+        let is_slash = |b: u8| b == b'/' || (special && b == b'\\');
+
+        // authority, special schemes other than `file` skip any
+        // number of slashes, `file` and the others take exactly two
+        let mut cursor = colon + 1;
+        let is_file = scheme.eq_ignore_ascii_case("file");
+        let has_authority = if special && !is_file {
+            while cursor < end && is_slash(bytes[cursor]) {
+                cursor += 1;
+            }
+            true
+        } else if end - cursor >= 2 && is_slash(bytes[cursor]) && is_slash(bytes[cursor + 1]) {
+            cursor += 2;
+            true
+        } else {
+            false
+        };
+        if has_authority {
+            let authority_end = (cursor..end)
+                .find(|i| is_slash(bytes[*i]) || bytes[*i] == b'?' || bytes[*i] == b'#')
+                .unwrap_or(end);
+            let authority = &input[cursor..authority_end];
+            if is_file {
+                // `file` has no userinfo or port, and a drive letter
+                // (`file://C:/`) starts the path instead
+                if !is_windows_drive_letter(authority) {
+                    spans.host = Some(cursor..authority_end);
+                    cursor = authority_end;
+                }
+            } else {
+                spans.scan_authority(input, cursor..authority_end);
+                cursor = authority_end;
+            }
+        }
+        // path, query, and fragment
+        let fragment_start = input[cursor..end].find('#').map(|i| cursor + i);
+        let path_end = fragment_start.unwrap_or(end);
+        let query_start = input[cursor..path_end].find('?').map(|i| cursor + i);
+        spans.path = non_empty(cursor..query_start.unwrap_or(path_end));
+        if let Some(query_start) = query_start {
+            spans.query = non_empty(query_start + 1..path_end);
+        }
+        if let Some(fragment_start) = fragment_start {
+            spans.fragment = non_empty(fragment_start + 1..end);
+        }
+        spans
+    }
+
+    /// `scan_authority` fills in the userinfo, host, and port within
+    /// `authority`. The host span is kept even when it is empty, as
+    /// in `foo:///path`.
+    fn scan_authority(&mut self, input: &str, authority: Range<usize>) {
+        let bytes = input.as_bytes();
+        let text = &input[authority.clone()];
+        let host_start = match text.rfind('@') {
+            Option::Some(at) => {
+                let userinfo = authority.start..authority.start + at;
+                match text[..at].find(':') {
+                    Option::Some(split) => {
+                        self.username = non_empty(userinfo.start..userinfo.start + split);
+                        self.password = non_empty(userinfo.start + split + 1..userinfo.end);
+                    }
+                    Option::None => self.username = non_empty(userinfo),
+                };
+                authority.start + at + 1
+            }
+            Option::None => authority.start,
+        };
+        let hostport = &input[host_start..authority.end];
+        let host_end = if hostport.starts_with('[') {
+            hostport.find(']').map(|i| host_start + i + 1).unwrap_or(authority.end)
+        } else {
+            hostport.find(':').map(|i| host_start + i).unwrap_or(authority.end)
+        };
+        self.host = Some(host_start..host_end);
+        if host_end < authority.end && bytes[host_end] == b':' {
+            self.port = non_empty(host_end + 1..authority.end);
+        }
+    }
+
+    /// `get` returns the span of a single component
+    pub fn get(&self, component: Component) -> Option<Range<usize>> {
+        match component {
+            Component::Scheme => self.scheme.clone(),
+            Component::Username => self.username.clone(),
+            Component::Password => self.password.clone(),
+            Component::Host => self.host.clone(),
+            Component::Port => self.port.clone(),
+            Component::Path => self.path.clone(),
+            Component::Query => self.query.clone(),
+            Component::Fragment => self.fragment.clone(),
+        }
+    }
+}
+
+#[inline(always)]
+fn non_empty(range: Range<usize>) -> Option<Range<usize>> {
+    if range.start < range.end { Some(range) } else { None }
+}
+
+/// `is_windows_drive_letter` checks for `C:` or `C|`, which the
+/// parser keeps in the path of a `file` URL.
+fn is_windows_drive_letter(text: &str) -> bool {
+    let bytes = text.as_bytes();
+    bytes.len() == 2 && bytes[0].is_ascii_alphabetic() && (bytes[1] == b':' || bytes[1] == b'|')
+}
+
+#[inline(always)]
+pub fn is_special(scheme: &str) -> bool {
+    matches!(scheme, "http" | "https" | "ws" | "wss" | "ftp" | "gopher" | "file")
+}
@@ -0,0 +1,28 @@
+use super::Url;
+
+impl Url {
+    /// `as_value` returns a `tracing` field value which records the
+    /// normalized string through `Visit::record_str`, without
+    /// allocating, and without any work at all when the event is
+    /// filtered out.
+    ///
+    /// `tracing::Value` is sealed, so it cannot be implemented for
+    /// `Url` directly. This is the next best thing.
+    ///
+    /// ```
+    /// #[macro_use]
+    /// extern crate tracing;
+    /// extern crate serde_url;
+    ///
+    /// use serde_url::Url;
+    ///
+    /// # fn main() {
+    /// let url = Url::new(&"https://google.com").unwrap();
+    /// info!(url = url.as_value(), "fetching");
+    /// info!(url = %url.as_display(), "fetching");
+    /// # }
+    /// ```
+    pub fn as_value<'a>(&'a self) -> &'a str {
+        self.get_string()
+    }
+}
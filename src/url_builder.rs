@@ -0,0 +1,127 @@
+use std::error::Error;
+use std::fmt;
+
+use super::errors::UrlFault;
+use super::parse_options::ParseOptions;
+use super::schema::{self, Schema};
+use super::Url;
+
+/// A check a `UrlBuilder` applies to the URL it builds
+pub type BuildValidator = Box<dyn Fn(&Url) -> Result<(), String> + Send + Sync>;
+
+/// Why a `UrlBuilder` did not build a `Url`
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum BuildError {
+    /// the input was not a valid URL, or was rejected by the
+    /// `Schema` or `ParseOptions`
+    Parse(UrlFault),
+    /// a validator rejected the URL, holding its message
+    PolicyViolation(String),
+}
+impl BuildError {
+    /// `is_input_problem` reports if the input should be rejected,
+    /// and reported to the user. A policy violation always is.
+    pub fn is_input_problem(&self) -> bool {
+        match *self {
+            BuildError::Parse(ref fault) => fault.is_input_problem(),
+            BuildError::PolicyViolation(_) => true,
+        }
+    }
+}
+impl From<UrlFault> for BuildError {
+    fn from(fault: UrlFault) -> BuildError {
+        BuildError::Parse(fault)
+    }
+}
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            BuildError::Parse(ref fault) => write!(f, "{}", fault),
+            BuildError::PolicyViolation(ref msg) => {
+                write!(f, "PolicyViolation URL was rejected by a validator: {}", msg)
+            }
+        }
+    }
+}
+impl Error for BuildError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            BuildError::Parse(ref fault) => Some(fault),
+            BuildError::PolicyViolation(_) => None,
+        }
+    }
+}
+
+/// Parses URLs with policy attached, so every caller builds URLs
+/// the same way
+///
+/// A builder holds only the policy, it is configured once and reused
+/// for every input. `build` parses an input (with the `Schema`, if
+/// one is given), applies the `ParseOptions`, then runs each
+/// validator in the order they were added. The first validator to
+/// fail stops the build, its message is returned as
+/// `BuildError::PolicyViolation`.
+///
+/// Validators, and the `Schema`, must be `Send + Sync`, so a builder
+/// can be shared between threads.
+///
+/// ```
+/// use serde_url::{UrlBuilder, BuildError, UrlFault, HttpsSchema, validators};
+///
+/// let builder = UrlBuilder::new()
+///     .schema(HttpsSchema)
+///     .validate_with(validators::no_credentials())
+///     .validate_with(validators::host_in(&["google.com", "api.google.com"]));
+/// assert_eq!(builder.build("https://api.google.com:443/v1").unwrap(), "https://api.google.com/v1");
+/// assert_eq!(builder.build("http://api.google.com/v1"), Err(BuildError::Parse(UrlFault::InvalidScheme)));
+/// assert_eq!(
+///     builder.build("https://mirror.org/v1"),
+///     Err(BuildError::PolicyViolation("host `mirror.org` is not allowed".to_string())));
+/// ```
+#[derive(Default)]
+pub struct UrlBuilder {
+    options: ParseOptions,
+    schema: Option<Box<dyn Schema + Send + Sync>>,
+    validators: Vec<BuildValidator>,
+}
+impl UrlBuilder {
+    /// `new` starts a builder with no policy
+    pub fn new() -> UrlBuilder {
+        UrlBuilder::default()
+    }
+
+    /// `options` sets the restrictions checked after parsing
+    pub fn options(mut self, options: ParseOptions) -> UrlBuilder {
+        self.options = options;
+        self
+    }
+
+    /// `schema` parses the input with `Url::new_with_schema`
+    pub fn schema<S: Schema + Send + Sync + 'static>(mut self, schema: S) -> UrlBuilder {
+        self.schema = Some(Box::new(schema));
+        self
+    }
+
+    /// `validate_with` adds a validator, run after every validator
+    /// added before it.
+    pub fn validate_with<F>(mut self, validator: F) -> UrlBuilder
+    where
+        F: Fn(&Url) -> Result<(), String> + Send + Sync + 'static,
+    {
+        self.validators.push(Box::new(validator));
+        self
+    }
+
+    /// `build` parses `input` then applies the policy
+    pub fn build(&self, input: &str) -> Result<Url, BuildError> {
+        let url = match self.schema {
+            Option::Some(ref schema) => schema::parse(input, schema.as_ref())?,
+            Option::None => Url::new(&input)?,
+        };
+        self.options.check(&url)?;
+        for validator in self.validators.iter() {
+            validator(&url).map_err(BuildError::PolicyViolation)?;
+        }
+        Ok(url)
+    }
+}
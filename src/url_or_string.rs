@@ -0,0 +1,127 @@
+use std::fmt;
+
+use super::serde;
+use super::validator;
+use super::Url;
+
+/// Either a `Url`, or a plain string which is not one
+///
+/// This replaces `#[serde(untagged)]` enums of `Url` and `String`.
+/// Those depend on how each format buffers its input, and discard
+/// why the `Url` variant failed. Deserializing a `UrlOrString` never
+/// fails on a string: input which parses, and passes the installed
+/// `DeserializeValidator`, becomes `Url`, anything else is kept,
+/// byte for byte, as `Other`. Serializing writes the normalized
+/// string of a `Url`, and `Other` unchanged.
+///
+/// Unlike `MaybeUrl` the parse fault is not kept, as a string which
+/// is not a URL is expected here, not an error.
+///
+/// ```
+/// extern crate serde_json;
+/// extern crate serde_url;
+///
+/// use serde_url::UrlOrString;
+///
+/// # fn main() {
+/// // instead of `#[serde(untagged)] enum LinkOrLabel { Link(Url), Label(String) }`
+/// let links: Vec<UrlOrString> = serde_json::from_str(r#"["https://google.com", "see below"]"#).unwrap();
+/// assert_eq!(links[0].as_url().unwrap(), "https://google.com/");
+/// assert_eq!(links[1], UrlOrString::Other("see below".to_string()));
+/// assert_eq!(serde_json::to_string(&links).unwrap(), r#"["https://google.com/","see below"]"#);
+/// # }
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum UrlOrString {
+    Url(Url),
+    Other(String),
+}
+impl UrlOrString {
+    /// `new` parses the input, keeping it as `Other` if it is not a
+    /// valid URL. The `DeserializeValidator` is not consulted.
+    pub fn new<S>(input: S) -> UrlOrString
+    where
+        S: AsRef<str> + Into<String>,
+    {
+        match Url::new(&input.as_ref()) {
+            Ok(url) => UrlOrString::Url(url),
+            Err(_) => UrlOrString::Other(input.into()),
+        }
+    }
+
+    /// `as_url` returns the `Url`, if this is one
+    pub fn as_url<'a>(&'a self) -> Option<&'a Url> {
+        match *self {
+            UrlOrString::Url(ref url) => Some(url),
+            UrlOrString::Other(_) => None,
+        }
+    }
+
+    /// `is_url` checks if this is a `Url`
+    pub fn is_url(&self) -> bool {
+        self.as_url().is_some()
+    }
+
+    /// `as_str` returns the normalized string of a `Url`, or the
+    /// other string as it was.
+    pub fn as_str<'a>(&'a self) -> &'a str {
+        match *self {
+            UrlOrString::Url(ref url) => url.get_string(),
+            UrlOrString::Other(ref other) => other.as_str(),
+        }
+    }
+
+    /// `from_input` is `new`, plus the `DeserializeValidator`
+    fn from_input(input: String) -> UrlOrString {
+        match Url::new(&input) {
+            Ok(url) if validator::validate(&url).is_ok() => UrlOrString::Url(url),
+            _ => UrlOrString::Other(input),
+        }
+    }
+}
+impl From<Url> for UrlOrString {
+    fn from(url: Url) -> UrlOrString {
+        UrlOrString::Url(url)
+    }
+}
+impl fmt::Display for UrlOrString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+impl serde::Serialize for UrlOrString {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+struct UrlOrStringVisitor;
+impl<'de> serde::de::Visitor<'de> for UrlOrStringVisitor {
+    type Value = UrlOrString;
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a string")
+    }
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(UrlOrString::from_input(value.to_string()))
+    }
+    fn visit_string<E>(self, value: String) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(UrlOrString::from_input(value))
+    }
+}
+impl<'de> serde::Deserialize<'de> for UrlOrString {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_string(UrlOrStringVisitor)
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::iter::FromIterator;
+use std::slice;
+
+use super::serde;
+use super::Url;
+
+/// A set of `Url`s supporting exact and path prefix lookups
+///
+/// URLs are grouped by origin, within each origin the paths are
+/// stored as a trie of (percent encoded) path segments. Looking a
+/// URL up costs one hash of its origin, and one step per segment
+/// of its path, regardless of how many URLs the set holds.
+///
+/// # Prefix Matching
+///
+/// A prefix only matches whole path segments, and ignores its own
+/// query and fragment. `/api` matches `/api`, `/api/`, and
+/// `/api/v1`, but not `/apis`. A prefix ending in `/` only matches
+/// the paths beneath it, so `/api/` does not match `/api`. URLs
+/// with an opaque origin (`mailto:`, `data:`, etc.) only support
+/// exact matches.
+///
+/// ```
+/// use serde_url::{Url, UrlSet};
+///
+/// let set: UrlSet = vec!["https://google.com/api", "https://google.com/api/v1/"]
+///     .into_iter()
+///     .map(|s| Url::new(&s).unwrap())
+///     .collect();
+///
+/// let url = Url::new(&"https://google.com/api/v1/users").unwrap();
+/// assert!(!set.contains_exact(&url));
+/// assert_eq!(set.contains_prefix_of(&url).unwrap(), "https://google.com/api/v1/");
+///
+/// let url = Url::new(&"https://google.com/apis").unwrap();
+/// assert_eq!(set.contains_prefix_of(&url), None);
+/// ```
+#[derive(Clone, Default)]
+pub struct UrlSet {
+    urls: Vec<Url>,
+    exact: HashSet<Url>,
+    origins: HashMap<String, Node>,
+}
+impl UrlSet {
+    /// `new` creates an empty `UrlSet`
+    pub fn new() -> UrlSet {
+        UrlSet::default()
+    }
+
+    /// `insert` adds a `Url`, returning `false` when it was
+    /// already present.
+    pub fn insert(&mut self, url: Url) -> bool {
+        if !self.exact.insert(url.clone()) {
+            return false;
+        }
+        if url.data.has_tuple_origin() {
+            let mut node = self.origins
+                .entry(url.data.get_origin_serialization())
+                .or_default();
+            for segment in segments(&url) {
+                node = node.children
+                    .entry(segment.to_string())
+                    .or_default();
+            }
+            if node.url.is_none() {
+                node.url = Some(url.clone());
+            }
+        }
+        self.urls.push(url);
+        true
+    }
+
+    /// `contains_exact` checks if this exact `Url` is in the set
+    pub fn contains_exact(&self, url: &Url) -> bool {
+        self.exact.contains(url)
+    }
+
+    /// `contains_prefix_of` returns the `Url` in the set with the
+    /// longest path which is a prefix of `url`'s path, under the
+    /// same origin.
+    pub fn contains_prefix_of<'a>(&'a self, url: &Url) -> Option<&'a Url> {
+        if !url.data.has_tuple_origin() {
+            return None;
+        }
+        let mut node = self.origins.get(&url.data.get_origin_serialization())?;
+        let mut best = None;
+        for segment in segments(url) {
+            // a prefix ending in `/` matches everything beneath it
+            if let Option::Some(dir) = node.children.get("").and_then(|dir| dir.url.as_ref()) {
+                best = Some(dir);
+            }
+            node = match node.children.get(segment) {
+                Option::Some(child) => child,
+                Option::None => return best,
+            };
+            if let Option::Some(ref url) = node.url {
+                best = Some(url);
+            }
+        }
+        best
+    }
+
+    /// `len` returns the number of `Url`s in the set
+    pub fn len(&self) -> usize {
+        self.urls.len()
+    }
+
+    /// `is_empty` checks if the set has no `Url`s
+    pub fn is_empty(&self) -> bool {
+        self.urls.is_empty()
+    }
+
+    /// `iter` returns the `Url`s in the order they were inserted
+    pub fn iter<'a>(&'a self) -> slice::Iter<'a, Url> {
+        self.urls.iter()
+    }
+}
+impl FromIterator<Url> for UrlSet {
+    fn from_iter<I>(iter: I) -> UrlSet
+    where
+        I: IntoIterator<Item = Url>,
+    {
+        let mut set = UrlSet::new();
+        set.extend(iter);
+        set
+    }
+}
+impl Extend<Url> for UrlSet {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = Url>,
+    {
+        for url in iter {
+            self.insert(url);
+        }
+    }
+}
+impl<'a> IntoIterator for &'a UrlSet {
+    type Item = &'a Url;
+    type IntoIter = slice::Iter<'a, Url>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl fmt::Debug for UrlSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.urls.iter()).finish()
+    }
+}
+impl serde::Serialize for UrlSet {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_seq(self.urls.iter())
+    }
+}
+impl<'de> serde::Deserialize<'de> for UrlSet {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Ok(Vec::<Url>::deserialize(deserializer)?.into_iter().collect())
+    }
+}
+
+/// A step in the segment trie of an origin
+#[derive(Clone, Default)]
+struct Node {
+    url: Option<Url>,
+    children: BTreeMap<String, Node>,
+}
+
+/// `segments` splits the normalized path, without the leading `/`
+fn segments<'a>(url: &'a Url) -> impl Iterator<Item = &'a str> + 'a {
+    let path = url.data.get_url_data().path();
+    let path = path.strip_prefix('/').unwrap_or(path);
+    path.split('/')
+}
@@ -0,0 +1,75 @@
+
+//! Process wide validation of deserialized URLs
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
+
+use super::Url;
+
+/// A check applied to every `Url` produced by `Deserialize`
+pub type DeserializeValidator = fn(&Url) -> Result<(), String>;
+
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+static VALIDATOR: RwLock<Option<DeserializeValidator>> = RwLock::new(None);
+
+/// `set_deserialize_validator` installs a check which every `Url`
+/// must pass when it is deserialized, replacing any previous one.
+/// An `Err` fails deserialization with its message. `Url::new` and
+/// `Url::from_str` never consult the validator.
+///
+/// # Global State
+///
+/// The validator is shared by every thread of the process, and by
+/// every library within it which deserializes a `Url`. Installing one
+/// affects all of them, including code running concurrently (unit
+/// tests are run in parallel threads by default). It should be set
+/// once, during startup, before any configuration is loaded.
+///
+/// ```
+/// extern crate serde_json;
+/// extern crate serde_url;
+///
+/// use serde_url::Url;
+///
+/// fn https_only(url: &Url) -> Result<(), String> {
+///     if url.get_scheme() == "https" {
+///         Ok(())
+///     } else {
+///         Err("only https is allowed".to_string())
+///     }
+/// }
+///
+/// # fn main() {
+/// serde_url::set_deserialize_validator(https_only);
+/// let err = serde_json::from_str::<Url>(r#""http://google.com/""#).unwrap_err();
+/// assert!(err.to_string().contains("only https is allowed"));
+/// serde_url::clear_deserialize_validator();
+/// # }
+/// ```
+pub fn set_deserialize_validator(validator: DeserializeValidator) {
+    let mut guard = VALIDATOR.write().unwrap_or_else(|e| e.into_inner());
+    *guard = Some(validator);
+    ACTIVE.store(true, Ordering::Release);
+}
+
+/// `clear_deserialize_validator` removes the installed validator,
+/// deserialization goes back to only parsing.
+pub fn clear_deserialize_validator() {
+    let mut guard = VALIDATOR.write().unwrap_or_else(|e| e.into_inner());
+    *guard = None;
+    ACTIVE.store(false, Ordering::Release);
+}
+
+/// `validate` runs the installed validator, if any. Without one
+/// this is a single atomic load.
+#[inline(always)]
+pub fn validate(url: &Url) -> Result<(), String> {
+    if !ACTIVE.load(Ordering::Acquire) {
+        return Ok(());
+    }
+    let validator = *VALIDATOR.read().unwrap_or_else(|e| e.into_inner());
+    match validator {
+        Option::Some(validator) => validator(url),
+        Option::None => Ok(()),
+    }
+}
@@ -0,0 +1,42 @@
+//! Prebuilt validators for `UrlBuilder::validate_with`
+
+use super::scheme::Scheme;
+use super::Url;
+
+/// `https_only` rejects every scheme but `https`
+pub fn https_only() -> impl Fn(&Url) -> Result<(), String> + Send + Sync {
+    |url: &Url| {
+        if url.scheme_is(Scheme::Https) {
+            Ok(())
+        } else {
+            Err(format!("scheme `{}` is not `https`", url.get_scheme()))
+        }
+    }
+}
+
+/// `no_credentials` rejects URLs with a username or password. The
+/// message never includes either.
+pub fn no_credentials() -> impl Fn(&Url) -> Result<(), String> + Send + Sync {
+    |url: &Url| {
+        let url_data = url.data.get_url_data();
+        if url_data.username().is_empty() && url_data.password().is_none() {
+            Ok(())
+        } else {
+            Err("URL contains credentials".to_string())
+        }
+    }
+}
+
+/// `host_in` rejects URLs whose host is not one of `hosts`, ignoring
+/// case. URLs without a host are rejected. Hosts are compared as they
+/// appear in the normalized string, so IPv6 hosts need brackets.
+pub fn host_in(hosts: &[&str]) -> impl Fn(&Url) -> Result<(), String> + Send + Sync {
+    let hosts = hosts.iter().map(|host| host.to_string()).collect::<Vec<_>>();
+    move |url: &Url| {
+        match url.data.get_url_data().host_str() {
+            Option::Some(host) if hosts.iter().any(|allowed| allowed.eq_ignore_ascii_case(host)) => Ok(()),
+            Option::Some(host) => Err(format!("host `{}` is not allowed", host)),
+            Option::None => Err("URL has no host".to_string()),
+        }
+    }
+}
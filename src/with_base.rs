@@ -0,0 +1,81 @@
+use std::fmt;
+
+use super::serde;
+use super::serde::de::{DeserializeSeed, Visitor};
+use super::errors::UrlFault;
+use super::validator;
+use super::Url;
+
+/// `DeserializeSeed` which resolves relative references against a
+/// base `Url`
+///
+/// Absolute URLs are read as usual. With a base, relative references
+/// (`/a`, `../b`, `?q=1`, `//host/c`) are resolved against it the
+/// same way a browser resolves a link. Without one this behaves
+/// exactly like `Url::deserialize`, and relative references are
+/// rejected.
+///
+/// The input of a resolved `Url` (`get_input()`) is the resolved
+/// absolute string, not the relative reference.
+///
+/// ```
+/// extern crate serde;
+/// extern crate serde_json;
+/// extern crate serde_url;
+///
+/// use serde::de::DeserializeSeed;
+/// use serde_url::{Url, UrlWithBase};
+///
+/// # fn main() {
+/// let base = Url::new(&"https://google.com/api/v1/").unwrap();
+/// let mut de = serde_json::Deserializer::from_str(r#""../v2/users?page=2""#);
+/// let url = UrlWithBase(Some(base)).deserialize(&mut de).unwrap();
+/// assert_eq!(url, "https://google.com/api/v2/users?page=2");
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct UrlWithBase(pub Option<Url>);
+impl UrlWithBase {
+    /// `resolve` parses `input`, relative to the base if there is one
+    pub fn resolve(&self, input: &str) -> Result<Url, UrlFault> {
+        match self.0 {
+            Option::Some(ref base) => {
+                let url_data = base.data.get_url_data()
+                    .join(input)
+                    .map_err(|err| UrlFault::from_parse_error(err, input))?;
+                Url::from_url_data(url_data)
+            }
+            Option::None => Url::new(&input),
+        }
+    }
+}
+impl<'de> DeserializeSeed<'de> for UrlWithBase {
+    type Value = Url;
+    fn deserialize<D>(self, deserializer: D) -> Result<Url, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_str(self)
+    }
+}
+impl<'de> Visitor<'de> for UrlWithBase {
+    type Value = Url;
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Option::Some(_) => write!(f, "Url, or a reference relative to the base Url"),
+            Option::None => write!(f, "Url"),
+        }
+    }
+    fn visit_str<E>(self, value: &str) -> Result<Url, E>
+    where
+        E: serde::de::Error,
+    {
+        let url = self.resolve(value)
+            .map_err(|e| format!("{:?}", e))
+            .map_err(serde::de::Error::custom)?;
+        validator::validate(&url)
+            .map_err(|msg| format!("{} ({})", msg, url))
+            .map_err(serde::de::Error::custom)?;
+        Ok(url)
+    }
+}
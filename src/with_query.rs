@@ -0,0 +1,180 @@
+
+//! A base URL plus a list of query pairs
+//!
+//! Configuration files often express an endpoint as a base URL and
+//! a list of query parameters, `UrlWithQuery` combines the two into
+//! a single `Url` while deserializing.
+//!
+//! ```
+//! extern crate serde_json;
+//! extern crate serde_url;
+//!
+//! use serde_url::with_query::UrlWithQuery;
+//!
+//! # fn main() {
+//! let data = r#"{"base":"https://google.com/search","query":[["q","rust lang"]]}"#;
+//! let url: UrlWithQuery = serde_json::from_str(data).unwrap();
+//! assert_eq!(url.get_string(), "https://google.com/search?q=rust+lang");
+//! # }
+//! ```
+//!
+//! # Note Merging
+//!
+//! Query pairs already present on `base` are kept, the listed
+//! pairs are appended after them. When serializing _every_ pair is
+//! written to `query`, so such a document does not round trip
+//! byte for byte, but it does produce an equal `Url`.
+
+use std::fmt;
+use std::ops;
+
+use super::serde;
+use super::serde::ser::SerializeStruct;
+use super::errors::UrlFault;
+use super::Url;
+
+/// A `Url` which (de)serializes as a base URL and a list of
+/// query pairs. See the module documentation.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct UrlWithQuery {
+    url: Url,
+}
+impl UrlWithQuery {
+    /// `new` appends the (unencoded) query pairs to `base`
+    ///
+    /// ```
+    /// use serde_url::Url;
+    /// use serde_url::with_query::UrlWithQuery;
+    ///
+    /// let base = Url::new(&"https://google.com/search?l=en").unwrap();
+    /// let pairs = vec![("q".to_string(), "a&b".to_string())];
+    /// let url = UrlWithQuery::new(&base, &pairs).unwrap();
+    /// assert_eq!(url.get_string(), "https://google.com/search?l=en&q=a%26b");
+    /// ```
+    pub fn new<K, V>(base: &Url, query: &[(K, V)]) -> Result<UrlWithQuery, UrlFault>
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        if query.is_empty() {
+            return Ok(UrlWithQuery { url: base.clone() });
+        }
+        let mut url_data = base.data.get_url_data().clone();
+        url_data.query_pairs_mut().extend_pairs(
+            query.iter().map(|(key, value)| (key.as_ref(), value.as_ref())),
+        );
+        let url = Url::from_url_data(url_data)?;
+        Ok(UrlWithQuery { url })
+    }
+
+    /// `get_base` returns the URL without any query
+    pub fn get_base(&self) -> Url {
+        self.url.strip_query()
+    }
+
+    /// `get_query` returns the decoded query pairs, keys without
+    /// a value are given an empty value.
+    pub fn get_query(&self) -> Vec<(String, String)> {
+        self.url
+            .query_iter()
+            .map(|(key, value)| (key.to_string(), value.unwrap_or("").to_string()))
+            .collect()
+    }
+
+    /// `into_url` returns the combined `Url`
+    pub fn into_url(self) -> Url {
+        self.url
+    }
+}
+impl From<Url> for UrlWithQuery {
+    fn from(url: Url) -> UrlWithQuery {
+        UrlWithQuery { url }
+    }
+}
+impl ops::Deref for UrlWithQuery {
+    type Target = Url;
+    fn deref<'a>(&'a self) -> &'a Url {
+        &self.url
+    }
+}
+impl fmt::Debug for UrlWithQuery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.url)
+    }
+}
+impl fmt::Display for UrlWithQuery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.url)
+    }
+}
+
+impl serde::Serialize for UrlWithQuery {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("UrlWithQuery", 2)?;
+        state.serialize_field("base", &self.get_base())?;
+        state.serialize_field("query", &self.get_query())?;
+        state.end()
+    }
+}
+
+/*
+ * Serde DeSerialize
+ *
+ * `base` is required, `query` defaults to an empty list.
+ */
+const FIELDS: &[&str] = &["base", "query"];
+
+struct UrlWithQueryVisitor;
+impl<'de> serde::de::Visitor<'de> for UrlWithQueryVisitor {
+    type Value = UrlWithQuery;
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "UrlWithQuery")
+    }
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        let mut base: Option<Url> = None;
+        let mut query: Option<Vec<(String, String)>> = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "base" => base = Some(map.next_value()?),
+                "query" => query = Some(map.next_value()?),
+                other => return Err(serde::de::Error::unknown_field(other, FIELDS)),
+            }
+        }
+        let base = base.ok_or_else(|| serde::de::Error::missing_field("base"))?;
+        build(&base, &query.unwrap_or_default())
+    }
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        let base: Url = seq
+            .next_element()?
+            .ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+        let query: Vec<(String, String)> = seq.next_element()?.unwrap_or_default();
+        build(&base, &query)
+    }
+}
+
+fn build<E>(base: &Url, query: &[(String, String)]) -> Result<UrlWithQuery, E>
+where
+    E: serde::de::Error,
+{
+    UrlWithQuery::new(base, query)
+        .map_err(|e| format!("{:?}", e))
+        .map_err(serde::de::Error::custom)
+}
+
+impl<'de> serde::Deserialize<'de> for UrlWithQuery {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_struct("UrlWithQuery", FIELDS, UrlWithQueryVisitor)
+    }
+}
@@ -0,0 +1,29 @@
+#![cfg(any(feature = "digest-sha256", feature = "digest-sha1", feature = "digest-md5"))]
+
+extern crate serde_url;
+
+#[allow(unused_imports)]
+use serde_url::{Url, DigestAlgorithm};
+
+#[test]
+#[cfg(feature = "digest-sha256")]
+fn sha256() {
+    let url = Url::new(&"HTTPS://Google.com").unwrap();
+    assert_eq!(url.get_digest_hex(DigestAlgorithm::Sha256), "9d116b1b0c1200ca75016e4c010bc94836366881b021a658ea7f8548b6543c1e");
+    assert_eq!(url.get_digest(DigestAlgorithm::Sha256)[..2], [0x9d, 0x11]);
+}
+
+#[test]
+#[cfg(feature = "digest-sha1")]
+fn sha1() {
+    let url = Url::new(&"https://google.com/").unwrap();
+    assert_eq!(url.get_digest_hex(DigestAlgorithm::Sha1), "e2428a1c3da816af8b8121b271f77a8175b3f33e");
+}
+
+#[test]
+#[cfg(feature = "digest-md5")]
+fn md5() {
+    let url = Url::new(&"https://google.com/").unwrap();
+    assert_eq!(url.get_digest_hex(DigestAlgorithm::Md5), "f82438a9862a39d642f39887b3e8e5b4");
+    assert_eq!(url.get_digest(DigestAlgorithm::Md5).len(), 16);
+}
@@ -0,0 +1,97 @@
+extern crate serde_url;
+
+use std::sync::Arc;
+use std::thread;
+
+use serde_url::{ParserCache, Url, UrlFault};
+
+#[test]
+fn cache_hits_share_storage() {
+    let cache = ParserCache::new(64, 128);
+    let a = cache.parse("https://google.com/a").unwrap();
+    let b = cache.parse("https://google.com/a").unwrap();
+    assert!(a.ptr_eq(&b));
+    assert!(!a.ptr_eq(&cache.parse("https://google.com/b").unwrap()));
+    assert_eq!(cache.len(), 2);
+
+    // different inputs are different keys, even if they normalize alike
+    let c = cache.parse("HTTPS://google.com/a").unwrap();
+    assert_eq!(a, c);
+    assert!(!a.ptr_eq(&c));
+    assert_eq!(c.get_input(), "HTTPS://google.com/a");
+
+    // failures are returned, never cached
+    assert_eq!(cache.parse("http://[::1").unwrap_err(), UrlFault::InvalidIpv6Address);
+    assert_eq!(cache.len(), 3);
+    cache.clear();
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn capacity_eviction() {
+    let cache = ParserCache::new(1, 128);
+    let a = cache.parse("https://google.com/a").unwrap();
+    let _ = cache.parse("https://google.com/b").unwrap();
+    assert_eq!(cache.len(), 1);
+    assert!(!a.ptr_eq(&cache.parse("https://google.com/a").unwrap()));
+
+    let cache = ParserCache::new(100, 128);
+    for i in 0..1000 {
+        cache.parse(&format!("https://google.com/{}", i)).unwrap();
+    }
+    assert_eq!(cache.len(), 100);
+
+    let cache = ParserCache::new(0, 128);
+    let a = cache.parse("https://google.com/a").unwrap();
+    assert!(!a.ptr_eq(&cache.parse("https://google.com/a").unwrap()));
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn lru_within_a_shard() {
+    // a single shard makes the order observable
+    let cache = ParserCache::new(1, 128);
+    let a = cache.parse("https://google.com/a").unwrap();
+    assert!(a.ptr_eq(&cache.parse("https://google.com/a").unwrap()));
+    let b = cache.parse("https://google.com/b").unwrap();
+    assert!(b.ptr_eq(&cache.parse("https://google.com/b").unwrap()));
+    assert!(!a.ptr_eq(&cache.parse("https://google.com/a").unwrap()));
+}
+
+#[test]
+fn oversized_keys_bypass() {
+    let cache = ParserCache::new(64, 20);
+    let long = "https://google.com/a/long/path";
+    let a = cache.parse(long).unwrap();
+    assert!(!a.ptr_eq(&cache.parse(long).unwrap()));
+    assert!(cache.is_empty());
+    let short = "https://g.co/";
+    assert!(cache.parse(short).unwrap().ptr_eq(&cache.parse(short).unwrap()));
+}
+
+#[test]
+fn hammer() {
+    let cache = Arc::new(ParserCache::new(32, 128));
+    let threads = (0..8)
+        .map(|t| {
+            let cache = cache.clone();
+            thread::spawn(move || {
+                for i in 0..2000 {
+                    let input = format!("https://google.com/{}", (i * (t + 1)) % 50);
+                    let url = cache.parse(&input).unwrap();
+                    assert_eq!(url.get_string(), input);
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+    assert!(cache.len() <= 32);
+}
+
+#[test]
+fn without_a_global_cache() {
+    let a = Url::new_cached("https://google.com/no_global_cache_here").unwrap();
+    assert_eq!(a, "https://google.com/no_global_cache_here");
+}
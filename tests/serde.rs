@@ -0,0 +1,515 @@
+
+#![allow(dead_code)]
+
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde_json;
+extern crate serde_yaml;
+extern crate bincode;
+extern crate toml;
+extern crate serde_url;
+
+use serde_url::Url;
+use serde_url::with_query::UrlWithQuery;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Endpoint {
+    name: String,
+    endpoint: UrlWithQuery,
+}
+
+#[test]
+fn with_query_json_round_trip() {
+    let data = r#"{"name":"search","endpoint":{"base":"https://google.com/search","query":[["q","rust lang"],["safe","on"]]}}"#;
+    let endpoint: Endpoint = serde_json::from_str(data).unwrap();
+    assert_eq!(endpoint.endpoint.get_string(), "https://google.com/search?q=rust+lang&safe=on");
+    assert_eq!(serde_json::to_string(&endpoint).unwrap(), data);
+}
+
+#[test]
+fn with_query_yaml_round_trip() {
+    let data = "name: search\nendpoint:\n  base: https://google.com/search\n  query:\n  - - q\n    - a&b=c\n";
+    let endpoint: Endpoint = serde_yaml::from_str(data).unwrap();
+    assert_eq!(endpoint.endpoint.get_string(), "https://google.com/search?q=a%26b%3Dc");
+    assert_eq!(endpoint.endpoint.get_query(), vec![("q".to_string(), "a&b=c".to_string())]);
+    assert_eq!(serde_yaml::to_string(&endpoint).unwrap(), data);
+}
+
+#[test]
+fn with_query_merges_existing_query() {
+    let data = r#"{"base":"https://google.com/search?l=en","query":[["q","1"]]}"#;
+    let url: UrlWithQuery = serde_json::from_str(data).unwrap();
+    assert_eq!(url.get_string(), "https://google.com/search?l=en&q=1");
+
+    // every pair is written to the list when serializing
+    let output = serde_json::to_string(&url).unwrap();
+    assert_eq!(output, r#"{"base":"https://google.com/search","query":[["l","en"],["q","1"]]}"#);
+    let again: UrlWithQuery = serde_json::from_str(&output).unwrap();
+    assert_eq!(again, url);
+}
+
+#[test]
+fn with_query_empty_pairs() {
+    let url: UrlWithQuery = serde_json::from_str(r#"{"base":"https://google.com/"}"#).unwrap();
+    assert_eq!(url.get_string(), "https://google.com/");
+    let url: UrlWithQuery = serde_json::from_str(r#"{"base":"https://google.com/","query":[]}"#).unwrap();
+    assert_eq!(url.get_string(), "https://google.com/");
+    assert_eq!(serde_json::to_string(&url).unwrap(), r#"{"base":"https://google.com/","query":[]}"#);
+    assert_eq!(url.clone().into_url(), Url::new(&"https://google.com/").unwrap());
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Feed {
+    #[serde(with = "serde_url::newline_list")]
+    urls: Vec<Url>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct LenientFeed {
+    #[serde(with = "serde_url::newline_list::lenient")]
+    urls: Vec<Url>,
+}
+
+#[test]
+fn newline_list_happy_path() {
+    let data = r#"{"urls":"  https://a.com/\n\n# comment\nhttps://b.com/x \n"}"#;
+    let feed: Feed = serde_json::from_str(data).unwrap();
+    assert_eq!(feed.urls.len(), 2);
+    assert_eq!(feed.urls[0], "https://a.com/");
+    assert_eq!(feed.urls[1], "https://b.com/x");
+}
+
+#[test]
+fn newline_list_bad_line() {
+    let data = r#"{"urls":"https://a.com/\n# comment\nhttp://[::1\nhttps://b.com/"}"#;
+    let err = serde_json::from_str::<Feed>(data).unwrap_err().to_string();
+    assert!(err.contains("line 3"), "{}", err);
+    assert!(err.contains("http://[::1"), "{}", err);
+
+    let feed: LenientFeed = serde_json::from_str(data).unwrap();
+    assert_eq!(feed.urls.len(), 2);
+}
+
+#[test]
+fn newline_list_crlf() {
+    let data = "{\"urls\":\"https://a.com/\\r\\nhttps://b.com/\\r\\n\"}";
+    let feed: Feed = serde_json::from_str(data).unwrap();
+    assert_eq!(feed.urls[0], "https://a.com/");
+    assert_eq!(feed.urls[1], "https://b.com/");
+}
+
+#[test]
+fn newline_list_round_trip() {
+    let feed = Feed {
+        urls: vec![Url::new(&"https://a.com").unwrap(), Url::new(&"https://b.com/x?y").unwrap()],
+    };
+    let data = serde_json::to_string(&feed).unwrap();
+    assert_eq!(data, r#"{"urls":"https://a.com/\nhttps://b.com/x?y"}"#);
+    assert_eq!(serde_json::from_str::<Feed>(&data).unwrap(), feed);
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Record {
+    id: u32,
+    homepage: serde_url::MaybeUrl,
+}
+
+#[test]
+fn maybe_url_round_trip() {
+    use serde_url::{MaybeUrl, UrlFault};
+
+    let data = r#"[{"id":1,"homepage":"https://google.com/a b"},{"id":2,"homepage":"http://[::1 garbage"},{"id":3,"homepage":""}]"#;
+    let records: Vec<Record> = serde_json::from_str(data).unwrap();
+
+    assert!(records[0].homepage.is_valid());
+    assert_eq!(records[0].homepage.as_url().unwrap().get_path_str(), Some("/a b"));
+    assert_eq!(records[1].homepage, MaybeUrl::Invalid {
+        input: "http://[::1 garbage".to_string(),
+        fault: UrlFault::InvalidIpv6Address,
+    });
+    assert_eq!(records[1].homepage.as_url(), None);
+    assert_eq!(records[2].homepage.clone().into_result(), Err(UrlFault::RelativeUrlWithoutBase));
+    assert_eq!(records[1].homepage.to_string(), "http://[::1 garbage");
+
+    // invalid input is written back unchanged, valid input normalized
+    let output = serde_json::to_string(&records).unwrap();
+    assert_eq!(output, r#"[{"id":1,"homepage":"https://google.com/a%20b"},{"id":2,"homepage":"http://[::1 garbage"},{"id":3,"homepage":""}]"#);
+    assert_eq!(serde_json::from_str::<Vec<Record>>(&output).unwrap(), records);
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct Webhook {
+    #[serde(with = "serde_url::strict_webhook")]
+    target: Url,
+}
+
+#[test]
+fn strict_webhook() {
+    let ok = r#"{"target":"https://hooks.google.com/notify?id=7"}"#;
+    let hook: Webhook = serde_json::from_str(ok).unwrap();
+    assert_eq!(serde_json::to_string(&hook).unwrap(), ok);
+
+    let err = serde_json::from_str::<Webhook>(r#"{"target":"https://hooks.google.com/notify#frag"}"#).unwrap_err().to_string();
+    assert!(err.contains("FragmentForbidden"), "{}", err);
+    assert!(err.contains("fragment `#frag`"), "{}", err);
+
+    let err = serde_json::from_str::<Webhook>(r#"{"target":"https://:hunter2@hooks.google.com/"}"#).unwrap_err().to_string();
+    assert!(err.contains("CredentialsForbidden"), "{}", err);
+    assert!(err.contains("a password"), "{}", err);
+    assert!(!err.contains("hunter2"), "{}", err);
+}
+
+#[test]
+fn origin_summary_snapshot() {
+    use serde_url::OriginSummary;
+
+    let summary: OriginSummary = vec!["https://google.com/a", "http://google.com:8080/", "mailto:a@google.com", "https://google.com/b"]
+        .into_iter()
+        .map(|s| Url::new(&s).unwrap())
+        .collect();
+    assert_eq!(
+        serde_json::to_string(&summary).unwrap(),
+        r#"{"http://google.com:8080":1,"https://google.com:443":2,"mailto:":1}"#);
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct LegacyRecord {
+    id: u32,
+    #[serde(with = "serde_url::compat::legacy_tuple")]
+    url: Url,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct LegacyBincodeRecord {
+    id: u32,
+    #[serde(with = "serde_url::compat::legacy_tuple::tuple_only")]
+    url: Url,
+}
+
+#[test]
+fn legacy_tuple() {
+    // an archive written by the old serializer
+    let blob = bincode::serialize(&(7u32, ("https", "google.com:8080", "/search?q=1"))).unwrap();
+    let record: LegacyBincodeRecord = bincode::deserialize(&blob).unwrap();
+    assert_eq!(record.id, 7);
+    assert_eq!(record.url, "https://google.com:8080/search?q=1");
+
+    // re-writing it produces the modern string form
+    let rewritten = bincode::serialize(&record).unwrap();
+    assert_eq!(rewritten, bincode::serialize(&(7u32, "https://google.com:8080/search?q=1")).unwrap());
+    let (id, url): (u32, Url) = bincode::deserialize(&rewritten).unwrap();
+    assert_eq!((id, url.get_string()), (7, "https://google.com:8080/search?q=1"));
+
+    // self-describing formats accept either form
+    let old: LegacyRecord = serde_json::from_str(r#"{"id":1,"url":["mailto","","a@google.com"]}"#).unwrap();
+    assert_eq!(old.url, "mailto:a@google.com");
+    let old: LegacyRecord = serde_json::from_str(r#"{"id":1,"url":["file","","/etc/hosts"]}"#).unwrap();
+    assert_eq!(old.url, "file:///etc/hosts");
+    let modern: LegacyRecord = serde_json::from_str(r#"{"id":2,"url":"https://google.com/a"}"#).unwrap();
+    assert_eq!(modern.url, "https://google.com/a");
+    assert_eq!(serde_json::to_string(&old).unwrap(), r#"{"id":1,"url":"file:///etc/hosts"}"#);
+    let yaml: LegacyRecord = serde_yaml::from_str("id: 3\nurl: [http, google.com, /]\n").unwrap();
+    assert_eq!(yaml.url, "http://google.com/");
+
+    // unknown arities, and invalid URLs, are clear errors
+    let err = serde_json::from_str::<LegacyRecord>(r#"{"id":1,"url":["https","google.com"]}"#).unwrap_err();
+    assert!(err.to_string().contains("must have 3 elements"), "{}", err);
+    assert!(err.to_string().contains("found 2"), "{}", err);
+    let err = serde_json::from_str::<LegacyRecord>(r#"{"id":1,"url":["https","google.com","/","x"]}"#).unwrap_err();
+    assert!(err.to_string().contains("found more than 3"), "{}", err);
+    let err = serde_json::from_str::<LegacyRecord>(r#"{"id":1,"url":["https","google.com:99999","/"]}"#).unwrap_err();
+    assert!(err.to_string().contains("\"https://google.com:99999/\""), "{}", err);
+    let err = serde_json::from_str::<LegacyRecord>(r#"{"id":1,"url":42}"#).unwrap_err();
+    assert!(err.to_string().contains("(scheme, authority, path_query) tuple"), "{}", err);
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct LosslessDocument {
+    name: String,
+    #[serde(with = "serde_url::lossless")]
+    home: Url,
+    #[serde(with = "serde_url::lossless")]
+    api: Url,
+}
+
+#[test]
+fn lossless_round_trip() {
+    let doc = r#"{"name":"search","home":"https://google.com/","api":"HTTP://API.Google.com:80/v1/../v2?q=a b"}"#;
+    let parsed: LosslessDocument = serde_json::from_str(doc).unwrap();
+    assert!(!parsed.home.input_differs());
+    assert!(parsed.api.input_differs());
+    assert_eq!(parsed.api, "http://api.google.com/v2?q=a%20b");
+    assert_eq!(serde_json::to_string(&parsed).unwrap(), doc);
+
+    // a `Url` built in memory writes its own input
+    let built = LosslessDocument {
+        name: "built".to_string(),
+        home: Url::new(&"https://google.com").unwrap(),
+        api: Url::new(&"https://google.com/v2").unwrap(),
+    };
+    let written = serde_json::to_string(&built).unwrap();
+    assert_eq!(written, r#"{"name":"built","home":"https://google.com","api":"https://google.com/v2"}"#);
+    assert_eq!(serde_json::from_str::<LosslessDocument>(&written).unwrap(), built);
+}
+
+#[test]
+fn recorded_input() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let hash = |url: &Url| {
+        let mut hasher = DefaultHasher::new();
+        url.hash(&mut hasher);
+        hasher.finish()
+    };
+    let url = Url::new(&"https://google.com/reset?token=REDACTED").unwrap();
+    let original = "https://google.com/reset?token=a1b2c3";
+    let audited = url.with_recorded_input(original);
+    assert_eq!(audited.get_input(), original);
+    assert!(audited.input_differs());
+    assert_eq!(audited.get_string(), url.get_string());
+    assert_eq!(audited.query_lookup_ci("token"), url.query_lookup_ci("token"));
+    assert_eq!(audited, url);
+    assert!(!audited.ptr_eq(&url));
+    assert_eq!(hash(&audited), hash(&url));
+    assert_eq!(url.get_input(), "https://google.com/reset?token=REDACTED");
+
+    // the label is not checked, and can be cleared again
+    let labelled = url.with_recorded_input("not a url at all");
+    assert_eq!(labelled.get_input(), "not a url at all");
+    let cleared = labelled.clear_recorded_input();
+    assert_eq!(cleared.get_input(), cleared.get_string());
+    assert_eq!(cleared, url);
+
+    // the label never replaces what was parsed
+    assert_eq!(audited.get_parsed_input(), "https://google.com/reset?token=REDACTED");
+    assert_eq!(labelled.input_host_span().map(|(_, s)| s), Some("google.com"));
+    assert!(!url.with_recorded_input("https://google.com/../../../etc").explain().has_warnings());
+    assert!(url.with_recorded_input("https://google.com/../../../etc").is_path_traversal_safe());
+
+    // serde writes the normalized form, `lossless` writes the label
+    // only when it parses to the same URL
+    assert_eq!(serde_json::to_string(&audited).unwrap(), r#""https://google.com/reset?token=REDACTED""#);
+    let lossless = |api: Url| {
+        let doc = LosslessDocument {
+            name: "audit".to_string(),
+            home: Url::new(&"https://google.com/").unwrap(),
+            api,
+        };
+        let written = serde_json::to_string(&doc).unwrap();
+        serde_json::from_str::<LosslessDocument>(&written).unwrap();
+        written
+    };
+    let written = lossless(audited);
+    assert!(written.contains(r#""api":"https://google.com/reset?token=REDACTED""#), "{}", written);
+    let written = lossless(labelled);
+    assert!(written.contains(r#""api":"https://google.com/reset?token=REDACTED""#), "{}", written);
+    let written = lossless(url.with_recorded_input("HTTPS://Google.com:443/reset?token=REDACTED"));
+    assert!(written.contains(r#""api":"HTTPS://Google.com:443/reset?token=REDACTED""#), "{}", written);
+}
+
+#[test]
+fn url_with_base_seed() {
+    use serde::de::DeserializeSeed;
+    use serde_url::{UrlFault, UrlWithBase};
+
+    let base = Url::new(&"https://google.com/docs/guide/index.html?v=1#top").unwrap();
+    let resolve = |seed: UrlWithBase, json: &str| {
+        let value: serde_json::Value = serde_json::from_str(json).unwrap();
+        seed.deserialize(value).map(|url| url.to_string()).map_err(|e| e.to_string())
+    };
+    let with_base = || UrlWithBase(Some(base.clone()));
+    assert_eq!(resolve(with_base(), r#""intro.html""#).unwrap(), "https://google.com/docs/guide/intro.html");
+    assert_eq!(resolve(with_base(), r#""../api/""#).unwrap(), "https://google.com/docs/api/");
+    assert_eq!(resolve(with_base(), r#""/root""#).unwrap(), "https://google.com/root");
+    assert_eq!(resolve(with_base(), r#""?v=2""#).unwrap(), "https://google.com/docs/guide/index.html?v=2");
+    assert_eq!(resolve(with_base(), r##""#faq""##).unwrap(), "https://google.com/docs/guide/index.html?v=1#faq");
+    assert_eq!(resolve(with_base(), r#""//cdn.google.com/a.js""#).unwrap(), "https://cdn.google.com/a.js");
+    assert_eq!(resolve(with_base(), r#""http://yahoo.com""#).unwrap(), "http://yahoo.com/");
+    assert!(resolve(with_base(), r#""http://[::1""#).unwrap_err().contains("InvalidIpv6Address"));
+    assert!(resolve(with_base(), "42").is_err());
+
+    // without a base this is plain deserialization
+    assert_eq!(resolve(UrlWithBase(None), r#""http://yahoo.com""#).unwrap(), "http://yahoo.com/");
+    assert!(resolve(UrlWithBase(None), r#""/root""#).unwrap_err().contains("RelativeUrlWithoutBase"));
+
+    // a seed can be used directly from a str deserializer too
+    let mut de = serde_json::Deserializer::from_str(r#""b/c""#);
+    assert_eq!(with_base().deserialize(&mut de).unwrap(), "https://google.com/docs/guide/b/c");
+    assert_eq!(with_base().resolve("../../x"), Ok(Url::new(&"https://google.com/x").unwrap()));
+    assert_eq!(UrlWithBase(None).resolve("x"), Err(UrlFault::RelativeUrlWithoutBase));
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct FlattenedService {
+    name: String,
+    endpoint: Url,
+    #[serde(flatten)]
+    extra: std::collections::BTreeMap<String, Url>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+struct StrictService {
+    name: String,
+    endpoint: Url,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(untagged)]
+enum Target {
+    Url(Url),
+    Named { name: String, url: Url },
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(tag = "kind")]
+enum Backend {
+    Http { url: Url },
+    Mirror { primary: Url, fallback: Url },
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Deployment {
+    service: FlattenedService,
+    strict: StrictService,
+    targets: Vec<Target>,
+    backends: Vec<Backend>,
+}
+
+fn deployment() -> Deployment {
+    let url = |s: &str| Url::new(&s).unwrap();
+    let mut extra = std::collections::BTreeMap::new();
+    extra.insert("health".to_string(), url("https://api.google.com/health"));
+    extra.insert("metrics".to_string(), url("https://api.google.com:9090/metrics"));
+    Deployment {
+        service: FlattenedService {
+            name: "api".to_string(),
+            endpoint: url("https://api.google.com/v1"),
+            extra,
+        },
+        strict: StrictService {
+            name: "auth".to_string(),
+            endpoint: url("https://auth.google.com/"),
+        },
+        targets: vec![
+            Target::Url(url("https://google.com/a")),
+            Target::Named { name: "b".to_string(), url: url("https://google.com/b?x=1") },
+        ],
+        backends: vec![
+            Backend::Http { url: url("http://10.0.0.1:8080/") },
+            Backend::Mirror { primary: url("https://a.google.com/"), fallback: url("https://b.google.com/") },
+        ],
+    }
+}
+
+#[test]
+fn buffered_derives_json() {
+    let expected = deployment();
+    let json = serde_json::to_string(&expected).unwrap();
+    assert!(json.contains(r#""health":"https://api.google.com/health""#), "{}", json);
+    assert_eq!(serde_json::from_str::<Deployment>(&json).unwrap(), expected);
+    // owned strings reach `visit_str` through serde's default `visit_string`
+    let value = serde_json::to_value(&expected).unwrap();
+    assert_eq!(serde_json::from_value::<Deployment>(value).unwrap(), expected);
+
+    let err = serde_json::from_str::<StrictService>(r#"{"name":"a","endpoint":"https://google.com/","extra":1}"#).unwrap_err();
+    assert!(err.to_string().contains("unknown field `extra`"), "{}", err);
+    let err = serde_json::from_str::<FlattenedService>(r#"{"name":"a","endpoint":"https://google.com/","x":"not a url"}"#).unwrap_err();
+    assert!(err.to_string().contains("RelativeUrlWithoutBase"), "{}", err);
+    let err = serde_json::from_str::<Backend>(r#"{"kind":"Http","url":"http://[::1"}"#).unwrap_err();
+    assert!(err.to_string().contains("InvalidIpv6Address"), "{}", err);
+}
+
+#[test]
+fn buffered_derives_toml() {
+    let expected = deployment();
+    let text = toml::to_string(&expected).unwrap();
+    assert!(text.contains(r#"endpoint = "https://auth.google.com/""#), "{}", text);
+    assert_eq!(toml::from_str::<Deployment>(&text).unwrap(), expected);
+
+    let err = toml::from_str::<StrictService>("name = \"a\"\nendpoint = \"https://google.com/\"\nextra = 1\n").unwrap_err();
+    assert!(err.to_string().contains("unknown field `extra`"), "{}", err);
+    let err = toml::from_str::<Backend>("kind = \"Http\"\nurl = \"nope\"\n").unwrap_err();
+    assert!(err.to_string().contains("RelativeUrlWithoutBase"), "{}", err);
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Page {
+    #[serde(with = "serde_url::scheme_relative")]
+    script: Url,
+    #[serde(with = "serde_url::scheme_relative")]
+    style: Url,
+}
+
+#[test]
+fn scheme_relative_round_trip() {
+    let page = Page {
+        script: Url::new(&"http://cdn.google.com/app.js?v=3").unwrap(),
+        style: Url::new(&"https://jane@cdn.google.com:8443/site.css#dark").unwrap(),
+    };
+    let json = serde_json::to_string(&page).unwrap();
+    assert_eq!(json, r##"{"script":"//cdn.google.com/app.js?v=3","style":"//jane@cdn.google.com:8443/site.css#dark"}"##);
+
+    // the scheme is lost, everything else survives
+    let read: Page = serde_json::from_str(&json).unwrap();
+    assert_eq!(read.script, "https://cdn.google.com/app.js?v=3");
+    assert_eq!(read.style, page.style);
+    assert_eq!(read.script.with_scheme("http").unwrap(), page.script);
+    assert_eq!(serde_json::to_string(&read).unwrap(), json);
+
+    // absolute URLs are read as they are
+    let read: Page = serde_json::from_str(r#"{"script":"http://a.google.com/x.js","style":"//b.google.com/y.css"}"#).unwrap();
+    assert_eq!(read.script, "http://a.google.com/x.js");
+    assert_eq!(read.style, "https://b.google.com/y.css");
+
+    let mail = Page {
+        script: Url::new(&"mailto:jane@google.com").unwrap(),
+        style: Url::new(&"https://google.com/").unwrap(),
+    };
+    let err = serde_json::to_string(&mail).unwrap_err();
+    assert!(err.to_string().contains("mailto:jane@google.com cannot be written scheme relative"), "{}", err);
+    let err = serde_json::from_str::<Page>(r#"{"script":"/x.js","style":"//google.com/"}"#).unwrap_err();
+    assert!(err.to_string().contains("RelativeUrlWithoutBase"), "{}", err);
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Link {
+    target: serde_url::UrlOrString,
+}
+
+#[test]
+fn url_or_string_round_trip() {
+    use serde_url::UrlOrString;
+
+    let data = r#"[{"target":"https://google.com/a b"},{"target":"http://[::1 garbage"},{"target":"  not a urlé "},{"target":""}]"#;
+    let links: Vec<Link> = serde_json::from_str(data).unwrap();
+    assert_eq!(links[0].target.as_url().unwrap(), "https://google.com/a%20b");
+    assert_eq!(links[1].target, UrlOrString::Other("http://[::1 garbage".to_string()));
+    assert_eq!(links[2].target, UrlOrString::Other("  not a url\u{e9} ".to_string()));
+    assert_eq!(links[3].target, UrlOrString::Other(String::new()));
+    assert!(links[0].target.is_url() && !links[1].target.is_url());
+
+    let output = serde_json::to_string(&links).unwrap();
+    assert_eq!(output, r#"[{"target":"https://google.com/a%20b"},{"target":"http://[::1 garbage"},{"target":"  not a urlé "},{"target":""}]"#);
+    assert_eq!(serde_json::from_str::<Vec<Link>>(&output).unwrap(), links);
+
+    let yaml = serde_yaml::to_string(&links).unwrap();
+    assert_eq!(serde_yaml::from_str::<Vec<Link>>(&yaml).unwrap(), links);
+
+    // also works where serde buffers the input
+    #[derive(Deserialize, Debug, PartialEq)]
+    #[serde(untagged)]
+    enum Field {
+        Count(u32),
+        Target(UrlOrString),
+    }
+    let fields: Vec<Field> = serde_json::from_str(r#"[3, "https://google.com", "label"]"#).unwrap();
+    assert_eq!(fields, vec![
+        Field::Count(3),
+        Field::Target(UrlOrString::new("https://google.com")),
+        Field::Target(UrlOrString::new("label")),
+    ]);
+    assert_eq!(UrlOrString::new("label").to_string(), "label");
+}
@@ -0,0 +1,66 @@
+#![cfg(feature = "signing")]
+
+extern crate serde_url;
+
+use serde_url::{Url, SignatureError};
+
+const KEY: &[u8] = b"correct horse battery staple";
+const NOW: u64 = 1_700_000_000;
+
+fn signed() -> Url {
+    Url::new(&"https://cdn.google.com/files/report.pdf?user=jane&download=1")
+        .unwrap()
+        .sign(KEY, NOW + 60)
+}
+
+#[test]
+fn sign_then_verify() {
+    let url = signed();
+    assert_eq!(url.verify_signature(KEY, NOW), Ok(()));
+    assert_eq!(url.verify_signature(KEY, NOW + 60), Ok(()));
+
+    // parameter order does not matter
+    let pairs = url.raw_query_pairs().collect::<Vec<_>>();
+    let reordered = pairs
+        .iter()
+        .rev()
+        .map(|&(key, value)| format!("{}={}", key, value.unwrap_or("")))
+        .collect::<Vec<_>>()
+        .join("&");
+    let reordered = Url::new(&format!("https://cdn.google.com/files/report.pdf?{}", reordered)).unwrap();
+    assert_eq!(reordered.verify_signature(KEY, NOW), Ok(()));
+
+    // signing again replaces the old signature
+    let resigned = url.sign(KEY, NOW + 120);
+    assert_eq!(resigned.raw_query_pairs().filter(|&(key, _)| key == "sig").count(), 1);
+    assert_eq!(resigned.verify_signature(KEY, NOW + 90), Ok(()));
+}
+
+#[test]
+fn tampered() {
+    let url = signed();
+
+    let path = Url::new(&url.get_string().replace("report.pdf", "secret.pdf")).unwrap();
+    assert_eq!(path.verify_signature(KEY, NOW), Err(SignatureError::Mismatch));
+
+    let query = Url::new(&url.get_string().replace("user=jane", "user=john")).unwrap();
+    assert_eq!(query.verify_signature(KEY, NOW), Err(SignatureError::Mismatch));
+
+    let expires = Url::new(&url.get_string().replace("expires=1700000060", "expires=1800000000")).unwrap();
+    assert_eq!(expires.verify_signature(KEY, NOW + 3600), Err(SignatureError::Mismatch));
+
+    assert_eq!(url.verify_signature(b"wrong key", NOW), Err(SignatureError::Mismatch));
+}
+
+#[test]
+fn expired() {
+    assert_eq!(signed().verify_signature(KEY, NOW + 61), Err(SignatureError::Expired));
+}
+
+#[test]
+fn missing() {
+    let url = Url::new(&"https://cdn.google.com/files/report.pdf?user=jane").unwrap();
+    assert_eq!(url.verify_signature(KEY, NOW), Err(SignatureError::Missing));
+    let url = Url::new(&"https://cdn.google.com/files/report.pdf?sig=00").unwrap();
+    assert_eq!(url.verify_signature(KEY, NOW), Err(SignatureError::Missing));
+}
@@ -0,0 +1,66 @@
+#![cfg(feature = "tracing")]
+
+#[macro_use]
+extern crate tracing;
+extern crate serde_url;
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+use serde_url::Url;
+
+/// name, value, and how each field was recorded
+type Fields = Arc<Mutex<Vec<(String, String, &'static str)>>>;
+
+#[derive(Clone, Default)]
+struct Recorder {
+    fields: Fields,
+}
+impl Visit for Recorder {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.fields.lock().unwrap().push((field.name().to_string(), value.to_string(), "str"));
+    }
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.fields.lock().unwrap().push((field.name().to_string(), format!("{:?}", value), "debug"));
+    }
+}
+impl Subscriber for Recorder {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        *metadata.level() <= tracing::Level::INFO
+    }
+    fn new_span(&self, _: &Attributes) -> Id {
+        Id::from_u64(1)
+    }
+    fn record(&self, _: &Id, _: &Record) {}
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+    fn event(&self, event: &Event) {
+        event.record(&mut self.clone());
+    }
+    fn enter(&self, _: &Id) {}
+    fn exit(&self, _: &Id) {}
+}
+
+#[test]
+fn url_fields() {
+    let recorder = Recorder::default();
+    let url = Url::new(&"HTTPS://Google.com/a b").unwrap();
+
+    tracing::subscriber::with_default(recorder.clone(), || {
+        info!(url = url.as_value(), "fetching");
+        info!(url = %url.as_display(), "fetching");
+        // filtered out, nothing is recorded
+        debug!(url = url.as_value(), "fetching");
+    });
+
+    let fields = recorder.fields.lock().unwrap();
+    let urls = fields
+        .iter()
+        .filter(|field| field.0 == "url")
+        .map(|field| (field.1.as_str(), field.2))
+        .collect::<Vec<_>>();
+    assert_eq!(urls, vec![("https://google.com/a%20b", "str"), ("https://google.com/a%20b", "debug")]);
+}
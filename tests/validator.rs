@@ -0,0 +1,80 @@
+
+//! The deserialize validator is process wide, so it is exercised
+//! from a single test in its own binary.
+
+#[macro_use]
+extern crate serde_derive;
+extern crate serde_json;
+extern crate serde_url;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use serde_url::{MaybeUrl, Url, UrlFault, UrlOrString};
+
+static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+fn https_only(url: &Url) -> Result<(), String> {
+    CALLS.fetch_add(1, Ordering::SeqCst);
+    if url.get_scheme() == "https" {
+        Ok(())
+    } else {
+        Err("only https is allowed".to_string())
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct Config {
+    upstream: Url,
+}
+
+#[derive(Deserialize, Debug)]
+struct LegacyConfig {
+    #[serde(with = "serde_url::compat::legacy_tuple")]
+    upstream: Url,
+}
+
+#[test]
+fn deserialize_validator() {
+    let data = r#"{"upstream":"http://google.com/"}"#;
+
+    // the default accepts anything which parses
+    assert!(serde_json::from_str::<Config>(data).is_ok());
+
+    serde_url::set_deserialize_validator(https_only);
+    let err = serde_json::from_str::<Config>(data).unwrap_err().to_string();
+    assert!(err.contains("only https is allowed"), "{}", err);
+    assert!(err.contains("http://google.com/"), "{}", err);
+    let config: Config = serde_json::from_str(r#"{"upstream":"https://google.com/"}"#).unwrap();
+    assert_eq!(config.upstream, "https://google.com/");
+
+    // so are the legacy forms, string and tuple alike
+    let err = serde_json::from_str::<LegacyConfig>(data).unwrap_err().to_string();
+    assert!(err.contains("only https is allowed"), "{}", err);
+    let legacy = r#"{"upstream":["http","google.com","/"]}"#;
+    let err = serde_json::from_str::<LegacyConfig>(legacy).unwrap_err().to_string();
+    assert!(err.contains("only https is allowed"), "{}", err);
+    let legacy = r#"{"upstream":["https","google.com","/"]}"#;
+    assert_eq!(serde_json::from_str::<LegacyConfig>(legacy).unwrap().upstream, "https://google.com/");
+
+    // the lenient types keep a rejected URL as a plain string
+    let urls = r#"["http://google.com/","https://google.com/"]"#;
+    let maybe: Vec<MaybeUrl> = serde_json::from_str(urls).unwrap();
+    assert_eq!(maybe[0], MaybeUrl::Invalid {
+        input: "http://google.com/".to_string(),
+        fault: UrlFault::ValidatorRejected,
+    });
+    assert!(maybe[1].is_valid());
+    assert_eq!(serde_json::to_string(&maybe).unwrap(), urls);
+    let either: Vec<UrlOrString> = serde_json::from_str(urls).unwrap();
+    assert_eq!(either[0], UrlOrString::Other("http://google.com/".to_string()));
+    assert!(either[1].is_url());
+
+    // Url::new is not validated
+    let calls = CALLS.load(Ordering::SeqCst);
+    assert!(Url::new(&"http://google.com/").is_ok());
+    assert_eq!(CALLS.load(Ordering::SeqCst), calls);
+
+    serde_url::clear_deserialize_validator();
+    assert!(serde_json::from_str::<Config>(data).is_ok());
+    assert_eq!(CALLS.load(Ordering::SeqCst), calls);
+}